@@ -1,10 +1,13 @@
 use wasm_bindgen::prelude::*;
 
-#[wasm_bindgen(js_namespace = ["window", "keplr"])]
+#[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(thread_local, js_namespace = window, js_name = keplr)]
+    #[wasm_bindgen(thread_local_v2, js_namespace = window, js_name = keplr)]
     pub static KEPLR: JsValue;
+}
 
+#[wasm_bindgen(js_namespace = ["window", "keplr"])]
+extern "C" {
     #[wasm_bindgen(js_name = ping, catch)]
     pub async fn ping() -> Result<(), JsValue>;
 
@@ -58,6 +61,117 @@ extern "C" {
     pub async fn sendTx(chainId: &str, tx: &[u8], mode: &str) -> Result<JsValue, JsValue>;
 }
 
+// A Keplr provider object, such as `window.keplr`. Unlike the free functions above, these
+// methods don't assume where the object lives, so any Keplr-compatible object can be wrapped.
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Clone, Debug)]
+    pub type Keplr;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn version(this: &Keplr) -> JsValue;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn mode(this: &Keplr) -> JsValue;
+
+    #[wasm_bindgen(method, js_name = ping, catch)]
+    pub async fn ping(this: &Keplr) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = enable, catch)]
+    pub async fn enable(this: &Keplr, chain_ids: Vec<String>) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = disable, catch)]
+    pub async fn disable(this: &Keplr, chain_id: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = disableOrigin)]
+    pub fn disable_origin(this: &Keplr);
+
+    #[wasm_bindgen(method, js_name = experimentalSuggestChain, catch)]
+    pub async fn suggest_chain(this: &Keplr, chain_info: JsValue) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = getKey, catch)]
+    pub async fn get_key(this: &Keplr, chain_id: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, js_name = getKeysSettled, catch)]
+    pub async fn get_keys_settled(this: &Keplr, chain_ids: Vec<String>)
+        -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, js_name = getOfflineSigner)]
+    pub fn get_offline_signer(
+        this: &Keplr,
+        chain_id: &str,
+        sign_options: JsValue, // KeplrSignOptions
+    ) -> KeplrOfflineSigner;
+
+    #[wasm_bindgen(method, js_name = getOfflineSignerOnlyAmino)]
+    pub fn get_offline_signer_only_amino(
+        this: &Keplr,
+        chain_id: &str,
+        sign_options: JsValue, // KeplrSignOptions
+    ) -> KeplrOfflineSignerOnlyAmino;
+
+    #[wasm_bindgen(method, js_name = getEnigmaUtils)]
+    pub fn get_enigma_utils(this: &Keplr, chain_id: &str) -> EnigmaUtils;
+
+    #[wasm_bindgen(method, js_name = suggestToken, catch)]
+    pub async fn suggest_token(
+        this: &Keplr,
+        chain_id: &str,
+        contract_address: &str,
+        viewing_key: Option<&str>,
+    ) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, js_name = getSecret20ViewingKey, catch)]
+    pub async fn get_secret_20_viewing_key(
+        this: &Keplr,
+        chain_id: &str,
+        contract_address: &str,
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, js_name = signAmino, catch)]
+    pub async fn sign_amino(
+        this: &Keplr,
+        chain_id: &str,
+        signer: &str,
+        sign_doc: JsValue,     // StdSignDoc
+        sign_options: JsValue, // KeplrSignOptions
+    ) -> Result<JsValue, JsValue>; // AminoSignResponse
+
+    #[wasm_bindgen(method, js_name = signDirect, catch)]
+    pub async fn sign_direct(
+        this: &Keplr,
+        chain_id: &str,
+        signer: &str,
+        sign_doc: JsValue,     // SignDoc
+        sign_options: JsValue, // KeplrSignOptions
+    ) -> Result<JsValue, JsValue>; // DirectSignResponse
+
+    #[wasm_bindgen(method, js_name = signArbitrary, catch)]
+    pub async fn sign_arbitrary(
+        this: &Keplr,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+    ) -> Result<JsValue, JsValue>; // StdSignature
+
+    #[wasm_bindgen(method, js_name = verifyArbitrary, catch)]
+    pub async fn verify_arbitrary(
+        this: &Keplr,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+        signature: JsValue, // StdSignature
+    ) -> Result<JsValue, JsValue>; // boolean
+
+    #[wasm_bindgen(method, js_name = sendTx, catch)]
+    pub async fn send_tx(
+        this: &Keplr,
+        chain_id: &str,
+        tx: &[u8],
+        mode: &str,
+    ) -> Result<JsValue, JsValue>;
+}
+
 #[wasm_bindgen(js_namespace = ["window", "keplr"])]
 extern "C" {
     pub type KeplrOfflineSigner;
@@ -85,7 +199,7 @@ extern "C" {
 
 impl std::fmt::Debug for KeplrOfflineSigner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "KeplrOfflineSigner: {:?}", self)
+        write!(f, "KeplrOfflineSigner: {:?}", self.obj)
     }
 }
 
@@ -109,7 +223,7 @@ extern "C" {
 
 impl std::fmt::Debug for KeplrOfflineSignerOnlyAmino {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "KeplrOfflineSignerOnlyAmino: {:?}", self)
+        write!(f, "KeplrOfflineSignerOnlyAmino: {:?}", self.obj)
     }
 }
 
@@ -134,16 +248,19 @@ extern "C" {
         msg: JsValue,
     ) -> Result<JsValue, JsValue>;
 
-    #[wasm_bindgen(method, js_name = getPubkey)]
-    pub async fn get_pubkey(this: &EnigmaUtils) -> JsValue;
+    #[wasm_bindgen(method, js_name = getPubkey, catch)]
+    pub async fn get_pubkey(this: &EnigmaUtils) -> Result<JsValue, JsValue>;
 
-    #[wasm_bindgen(method, js_name = getTxEncryptionKey)]
-    pub async fn get_tx_encryption_key(this: &EnigmaUtils, nonce: &[u8]) -> JsValue;
+    #[wasm_bindgen(method, js_name = getTxEncryptionKey, catch)]
+    pub async fn get_tx_encryption_key(
+        this: &EnigmaUtils,
+        nonce: &[u8],
+    ) -> Result<JsValue, JsValue>;
 }
 
 impl std::fmt::Debug for EnigmaUtils {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "EnigmaUtils: {:?}", self)
+        write!(f, "EnigmaUtils: {:?}", self.obj)
     }
 }
 
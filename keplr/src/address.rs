@@ -0,0 +1,110 @@
+//! Bech32 address encoding and derivation of account addresses from public keys.
+
+use crate::{AccountData, Algo, Error, Key};
+use bech32::{Bech32, Hrp};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Encodes `data` as a bech32 string with the given human-readable prefix.
+pub fn encode(prefix: &str, data: &[u8]) -> Result<String, Error> {
+    let hrp = Hrp::parse(prefix).map_err(|e| Error::Address(e.to_string()))?;
+    bech32::encode::<Bech32>(hrp, data).map_err(|e| Error::Address(e.to_string()))
+}
+
+/// Decodes a bech32 string into its prefix and data.
+pub fn decode(address: &str) -> Result<(String, Vec<u8>), Error> {
+    let (hrp, data) = bech32::decode(address).map_err(|e| Error::Address(e.to_string()))?;
+    Ok((hrp.to_lowercase(), data))
+}
+
+/// Re-encodes a bech32 address with a different prefix, e.g. `cosmos1...` to `secret1...`.
+pub fn convert_prefix(address: &str, prefix: &str) -> Result<String, Error> {
+    let (_, data) = decode(address)?;
+    encode(prefix, &data)
+}
+
+/// Derives the raw account address from a public key.
+///
+/// - `secp256k1`: `ripemd160(sha256(compressed pubkey))`
+/// - `ethsecp256k1`: last 20 bytes of `keccak256(uncompressed pubkey)`
+/// - `ed25519`: first 20 bytes of `sha256(pubkey)`
+pub fn pubkey_to_address(algo: Algo, pubkey: &[u8]) -> Result<Vec<u8>, Error> {
+    match algo {
+        Algo::Secp256k1 => {
+            let pubkey = k256::PublicKey::from_sec1_bytes(pubkey)
+                .map_err(|_| Error::Address("invalid secp256k1 public key".to_string()))?;
+            let sha = Sha256::digest(pubkey.to_encoded_point(true).as_bytes());
+            Ok(Ripemd160::digest(sha).to_vec())
+        }
+        Algo::Ethsecp256k1 => {
+            let pubkey = k256::PublicKey::from_sec1_bytes(pubkey)
+                .map_err(|_| Error::Address("invalid secp256k1 public key".to_string()))?;
+            let uncompressed = pubkey.to_encoded_point(false);
+            // skip the 0x04 tag byte
+            let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+            Ok(hash[12..].to_vec())
+        }
+        Algo::Ed25519 => {
+            if pubkey.len() != 32 {
+                return Err(Error::Address("invalid ed25519 public key".to_string()));
+            }
+            Ok(Sha256::digest(pubkey)[..20].to_vec())
+        }
+        Algo::Sr25519 => Err(Error::Address(
+            "address derivation is not supported for sr25519".to_string(),
+        )),
+    }
+}
+
+/// Derives the bech32 address of a public key for the given prefix.
+pub fn pubkey_to_bech32(algo: Algo, pubkey: &[u8], prefix: &str) -> Result<String, Error> {
+    encode(prefix, &pubkey_to_address(algo, pubkey)?)
+}
+
+fn verify(algo: Algo, pubkey: &[u8], bech32_address: &str) -> Result<(), Error> {
+    let (_, data) = decode(bech32_address)?;
+    if pubkey_to_address(algo, pubkey)? != data {
+        return Err(Error::Address(format!(
+            "{bech32_address} does not match the public key"
+        )));
+    }
+    Ok(())
+}
+
+impl Key {
+    /// Parses the `algo` field reported by Keplr.
+    pub fn algo(&self) -> Result<Algo, Error> {
+        self.algo.parse()
+    }
+
+    /// Checks that `bech32_address` and `address` were derived from `pub_key`.
+    pub fn verify_address(&self) -> Result<(), Error> {
+        verify(self.algo()?, &self.pub_key, &self.bech32_address)?;
+        let (_, data) = decode(&self.bech32_address)?;
+        if data != self.address {
+            return Err(Error::Address(
+                "address bytes do not match the bech32 address".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the same account's address with another chain's bech32 prefix.
+    pub fn address_with_prefix(&self, prefix: &str) -> Result<String, Error> {
+        encode(prefix, &self.address)
+    }
+}
+
+impl AccountData {
+    /// Checks that `address` was derived from `pubkey`.
+    pub fn verify_address(&self) -> Result<(), Error> {
+        verify(self.algo, &self.pubkey, &self.address)
+    }
+
+    /// Returns the same account's address with another chain's bech32 prefix.
+    pub fn address_with_prefix(&self, prefix: &str) -> Result<String, Error> {
+        convert_prefix(&self.address, prefix)
+    }
+}
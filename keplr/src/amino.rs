@@ -0,0 +1,573 @@
+//! Conversion of protobuf messages to and from their amino JSON form, needed to sign with
+//! [`KeplrOfflineSignerOnlyAmino`](crate::KeplrOfflineSignerOnlyAmino).
+
+use crate::{
+    address,
+    proto::{ibc_transfer, secret_compute},
+    sign_types::AminoMsg,
+    Error,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use cosmrs::{
+    proto::{
+        cosmos::{bank, base::v1beta1::Coin, distribution, gov, staking},
+        cosmwasm::wasm,
+        prost::Name,
+    },
+    Any,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const SECRET_PREFIX: &str = "secret";
+
+/// Converts one protobuf message type to and from amino JSON.
+pub trait AminoConverter {
+    /// Protobuf type URL, e.g. `/cosmos.bank.v1beta1.MsgSend`.
+    fn type_url(&self) -> String;
+
+    /// Amino type name, e.g. `cosmos-sdk/MsgSend`.
+    fn amino_type(&self) -> &str;
+
+    /// Converts the message into the `value` of an [`AminoMsg`].
+    fn to_amino(&self, msg: &Any) -> Result<Value, Error>;
+
+    /// Converts the `value` of an [`AminoMsg`] back into the message.
+    fn to_proto(&self, value: &Value) -> Result<Any, Error>;
+}
+
+/// An [`AminoConverter`] for a generated protobuf type `M`, defined by a pair of functions.
+pub struct ProtoConverter<M> {
+    amino_type: &'static str,
+    to_amino: fn(M) -> Result<Value, Error>,
+    from_amino: fn(&Value) -> Result<M, Error>,
+}
+
+impl<M> ProtoConverter<M> {
+    pub const fn new(
+        amino_type: &'static str,
+        to_amino: fn(M) -> Result<Value, Error>,
+        from_amino: fn(&Value) -> Result<M, Error>,
+    ) -> Self {
+        Self {
+            amino_type,
+            to_amino,
+            from_amino,
+        }
+    }
+}
+
+impl<M: cosmrs::proto::prost::Message + Name + Default> AminoConverter for ProtoConverter<M> {
+    fn type_url(&self) -> String {
+        M::type_url()
+    }
+
+    fn amino_type(&self) -> &str {
+        self.amino_type
+    }
+
+    fn to_amino(&self, msg: &Any) -> Result<Value, Error> {
+        let msg = msg.to_msg::<M>().map_err(|e| Error::Tx(e.to_string()))?;
+        (self.to_amino)(msg)
+    }
+
+    fn to_proto(&self, value: &Value) -> Result<Any, Error> {
+        let msg = (self.from_amino)(value)?;
+        Any::from_msg(&msg).map_err(|e| Error::Tx(e.to_string()))
+    }
+}
+
+/// Amino converters, looked up by protobuf type URL when signing and by amino type when reading
+/// the signed doc back.
+pub struct AminoRegistry {
+    by_type_url: HashMap<String, Box<dyn AminoConverter>>,
+    by_amino_type: HashMap<String, String>,
+}
+
+impl Default for AminoRegistry {
+    /// A registry with the built-in Cosmos SDK, IBC transfer and CosmWasm converters.
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register_cosmos_sdk();
+        registry.register_cosmwasm();
+        registry
+    }
+}
+
+impl AminoRegistry {
+    /// A registry without any converters.
+    pub fn empty() -> Self {
+        Self {
+            by_type_url: HashMap::new(),
+            by_amino_type: HashMap::new(),
+        }
+    }
+
+    /// A registry for Secret Network, with its `compute` module in place of CosmWasm. Both use the
+    /// `wasm/*` amino types, so they can't share a registry.
+    pub fn secret() -> Self {
+        let mut registry = Self::empty();
+        registry.register_cosmos_sdk();
+        registry.register_secret_compute();
+        registry
+    }
+
+    /// Adds a converter, replacing any converter for the same type URL or amino type.
+    pub fn register(&mut self, converter: impl AminoConverter + 'static) {
+        let type_url = converter.type_url();
+        let amino_type = converter.amino_type().to_string();
+        if let Some(previous) = self
+            .by_amino_type
+            .insert(amino_type.clone(), type_url.clone())
+        {
+            self.by_type_url.remove(&previous);
+        }
+        if let Some(previous) = self.by_type_url.insert(type_url, Box::new(converter)) {
+            if previous.amino_type() != amino_type {
+                self.by_amino_type.remove(previous.amino_type());
+            }
+        }
+    }
+
+    pub fn to_amino(&self, msg: &Any) -> Result<AminoMsg, Error> {
+        let converter = self
+            .by_type_url
+            .get(&msg.type_url)
+            .ok_or_else(|| Error::Tx(format!("no amino converter for {}", msg.type_url)))?;
+        Ok(AminoMsg {
+            type_: converter.amino_type().to_string(),
+            value: converter.to_amino(msg)?,
+        })
+    }
+
+    pub fn to_proto(&self, msg: &AminoMsg) -> Result<Any, Error> {
+        self.by_amino_type
+            .get(&msg.type_)
+            .and_then(|type_url| self.by_type_url.get(type_url))
+            .ok_or_else(|| Error::Tx(format!("no amino converter for {}", msg.type_)))?
+            .to_proto(&msg.value)
+    }
+
+    fn register_cosmos_sdk(&mut self) {
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgSend",
+            |msg: bank::v1beta1::MsgSend| {
+                Ok(json!({
+                    "from_address": msg.from_address,
+                    "to_address": msg.to_address,
+                    "amount": coins_to_amino(&msg.amount),
+                }))
+            },
+            |value| {
+                Ok(bank::v1beta1::MsgSend {
+                    from_address: string(value, "from_address")?,
+                    to_address: string(value, "to_address")?,
+                    amount: coins_from_amino(value, "amount")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgDelegate",
+            |msg: staking::v1beta1::MsgDelegate| {
+                Ok(json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                    "amount": msg.amount.as_ref().map(coin_to_amino),
+                }))
+            },
+            |value| {
+                Ok(staking::v1beta1::MsgDelegate {
+                    delegator_address: string(value, "delegator_address")?,
+                    validator_address: string(value, "validator_address")?,
+                    amount: Some(coin_from_amino(field(value, "amount")?)?),
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgUndelegate",
+            |msg: staking::v1beta1::MsgUndelegate| {
+                Ok(json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                    "amount": msg.amount.as_ref().map(coin_to_amino),
+                }))
+            },
+            |value| {
+                Ok(staking::v1beta1::MsgUndelegate {
+                    delegator_address: string(value, "delegator_address")?,
+                    validator_address: string(value, "validator_address")?,
+                    amount: Some(coin_from_amino(field(value, "amount")?)?),
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgBeginRedelegate",
+            |msg: staking::v1beta1::MsgBeginRedelegate| {
+                Ok(json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_src_address": msg.validator_src_address,
+                    "validator_dst_address": msg.validator_dst_address,
+                    "amount": msg.amount.as_ref().map(coin_to_amino),
+                }))
+            },
+            |value| {
+                Ok(staking::v1beta1::MsgBeginRedelegate {
+                    delegator_address: string(value, "delegator_address")?,
+                    validator_src_address: string(value, "validator_src_address")?,
+                    validator_dst_address: string(value, "validator_dst_address")?,
+                    amount: Some(coin_from_amino(field(value, "amount")?)?),
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgWithdrawDelegationReward",
+            |msg: distribution::v1beta1::MsgWithdrawDelegatorReward| {
+                Ok(json!({
+                    "delegator_address": msg.delegator_address,
+                    "validator_address": msg.validator_address,
+                }))
+            },
+            |value| {
+                Ok(distribution::v1beta1::MsgWithdrawDelegatorReward {
+                    delegator_address: string(value, "delegator_address")?,
+                    validator_address: string(value, "validator_address")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgModifyWithdrawAddress",
+            |msg: distribution::v1beta1::MsgSetWithdrawAddress| {
+                Ok(json!({
+                    "delegator_address": msg.delegator_address,
+                    "withdraw_address": msg.withdraw_address,
+                }))
+            },
+            |value| {
+                Ok(distribution::v1beta1::MsgSetWithdrawAddress {
+                    delegator_address: string(value, "delegator_address")?,
+                    withdraw_address: string(value, "withdraw_address")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgWithdrawValCommission",
+            |msg: distribution::v1beta1::MsgWithdrawValidatorCommission| {
+                Ok(json!({ "validator_address": msg.validator_address }))
+            },
+            |value| {
+                Ok(distribution::v1beta1::MsgWithdrawValidatorCommission {
+                    validator_address: string(value, "validator_address")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgFundCommunityPool",
+            |msg: distribution::v1beta1::MsgFundCommunityPool| {
+                Ok(json!({
+                    "amount": coins_to_amino(&msg.amount),
+                    "depositor": msg.depositor,
+                }))
+            },
+            |value| {
+                Ok(distribution::v1beta1::MsgFundCommunityPool {
+                    amount: coins_from_amino(value, "amount")?,
+                    depositor: string(value, "depositor")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgVote",
+            |msg: gov::v1beta1::MsgVote| {
+                Ok(json!({
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "voter": msg.voter,
+                    "option": msg.option,
+                }))
+            },
+            |value| {
+                Ok(gov::v1beta1::MsgVote {
+                    proposal_id: number(value, "proposal_id")?,
+                    voter: string(value, "voter")?,
+                    option: number(value, "option")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgDeposit",
+            |msg: gov::v1beta1::MsgDeposit| {
+                Ok(json!({
+                    "proposal_id": msg.proposal_id.to_string(),
+                    "depositor": msg.depositor,
+                    "amount": coins_to_amino(&msg.amount),
+                }))
+            },
+            |value| {
+                Ok(gov::v1beta1::MsgDeposit {
+                    proposal_id: number(value, "proposal_id")?,
+                    depositor: string(value, "depositor")?,
+                    amount: coins_from_amino(value, "amount")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "cosmos-sdk/MsgTransfer",
+            |msg: ibc_transfer::MsgTransfer| {
+                // amino omits default values, but always includes the (possibly empty) height
+                let mut timeout_height = json!({});
+                if let Some(height) = msg.timeout_height {
+                    if height.revision_number != 0 {
+                        timeout_height["revision_number"] =
+                            json!(height.revision_number.to_string());
+                    }
+                    if height.revision_height != 0 {
+                        timeout_height["revision_height"] =
+                            json!(height.revision_height.to_string());
+                    }
+                }
+                let mut value = json!({
+                    "source_port": msg.source_port,
+                    "source_channel": msg.source_channel,
+                    "token": msg.token.as_ref().map(coin_to_amino),
+                    "sender": msg.sender,
+                    "receiver": msg.receiver,
+                    "timeout_height": timeout_height,
+                });
+                if msg.timeout_timestamp != 0 {
+                    value["timeout_timestamp"] = json!(msg.timeout_timestamp.to_string());
+                }
+                if !msg.memo.is_empty() {
+                    value["memo"] = json!(msg.memo);
+                }
+                Ok(value)
+            },
+            |value| {
+                // an empty height means the transfer only times out by timestamp
+                let timeout_height = field(value, "timeout_height")?;
+                let timeout_height = Some(ibc_transfer::Height {
+                    revision_number: optional_number(timeout_height, "revision_number")?,
+                    revision_height: optional_number(timeout_height, "revision_height")?,
+                })
+                .filter(|height| height.revision_number != 0 || height.revision_height != 0);
+                Ok(ibc_transfer::MsgTransfer {
+                    source_port: string(value, "source_port")?,
+                    source_channel: string(value, "source_channel")?,
+                    token: Some(coin_from_amino(field(value, "token")?)?),
+                    sender: string(value, "sender")?,
+                    receiver: string(value, "receiver")?,
+                    timeout_height,
+                    timeout_timestamp: optional_number(value, "timeout_timestamp")?,
+                    memo: optional_string(value, "memo")?,
+                })
+            },
+        ));
+    }
+
+    fn register_cosmwasm(&mut self) {
+        self.register(ProtoConverter::new(
+            "wasm/MsgExecuteContract",
+            |msg: wasm::v1::MsgExecuteContract| {
+                Ok(json!({
+                    "sender": msg.sender,
+                    "contract": msg.contract,
+                    "msg": json_from_bytes(&msg.msg)?,
+                    "funds": coins_to_amino(&msg.funds),
+                }))
+            },
+            |value| {
+                Ok(wasm::v1::MsgExecuteContract {
+                    sender: string(value, "sender")?,
+                    contract: string(value, "contract")?,
+                    msg: json_to_bytes(field(value, "msg")?)?,
+                    funds: coins_from_amino(value, "funds")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "wasm/MsgInstantiateContract",
+            |msg: wasm::v1::MsgInstantiateContract| {
+                let mut value = json!({
+                    "sender": msg.sender,
+                    "code_id": msg.code_id.to_string(),
+                    "label": msg.label,
+                    "msg": json_from_bytes(&msg.msg)?,
+                    "funds": coins_to_amino(&msg.funds),
+                });
+                if !msg.admin.is_empty() {
+                    value["admin"] = json!(msg.admin);
+                }
+                Ok(value)
+            },
+            |value| {
+                Ok(wasm::v1::MsgInstantiateContract {
+                    sender: string(value, "sender")?,
+                    admin: optional_string(value, "admin")?,
+                    code_id: number(value, "code_id")?,
+                    label: string(value, "label")?,
+                    msg: json_to_bytes(field(value, "msg")?)?,
+                    funds: coins_from_amino(value, "funds")?,
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "wasm/MsgMigrateContract",
+            |msg: wasm::v1::MsgMigrateContract| {
+                Ok(json!({
+                    "sender": msg.sender,
+                    "contract": msg.contract,
+                    "code_id": msg.code_id.to_string(),
+                    "msg": json_from_bytes(&msg.msg)?,
+                }))
+            },
+            |value| {
+                Ok(wasm::v1::MsgMigrateContract {
+                    sender: string(value, "sender")?,
+                    contract: string(value, "contract")?,
+                    code_id: number(value, "code_id")?,
+                    msg: json_to_bytes(field(value, "msg")?)?,
+                })
+            },
+        ));
+    }
+
+    fn register_secret_compute(&mut self) {
+        self.register(ProtoConverter::new(
+            "wasm/MsgExecuteContract",
+            |msg: secret_compute::MsgExecuteContract| {
+                Ok(json!({
+                    "sender": address::encode(SECRET_PREFIX, &msg.sender)?,
+                    "contract": address::encode(SECRET_PREFIX, &msg.contract)?,
+                    "msg": BASE64_STANDARD.encode(&msg.msg),
+                    "sent_funds": coins_to_amino(&msg.sent_funds),
+                }))
+            },
+            |value| {
+                Ok(secret_compute::MsgExecuteContract {
+                    sender: address_bytes(value, "sender")?,
+                    contract: address_bytes(value, "contract")?,
+                    msg: base64_bytes(value, "msg")?,
+                    sent_funds: coins_from_amino(value, "sent_funds")?,
+                    ..Default::default()
+                })
+            },
+        ));
+        self.register(ProtoConverter::new(
+            "wasm/MsgInstantiateContract",
+            |msg: secret_compute::MsgInstantiateContract| {
+                let mut value = json!({
+                    "sender": address::encode(SECRET_PREFIX, &msg.sender)?,
+                    "code_id": msg.code_id.to_string(),
+                    "label": msg.label,
+                    "init_msg": BASE64_STANDARD.encode(&msg.init_msg),
+                    "init_funds": coins_to_amino(&msg.init_funds),
+                });
+                if !msg.admin.is_empty() {
+                    value["admin"] = json!(msg.admin);
+                }
+                Ok(value)
+            },
+            |value| {
+                Ok(secret_compute::MsgInstantiateContract {
+                    sender: address_bytes(value, "sender")?,
+                    code_id: number(value, "code_id")?,
+                    label: string(value, "label")?,
+                    init_msg: base64_bytes(value, "init_msg")?,
+                    init_funds: coins_from_amino(value, "init_funds")?,
+                    admin: optional_string(value, "admin")?,
+                    ..Default::default()
+                })
+            },
+        ));
+    }
+}
+
+impl std::fmt::Debug for AminoRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.by_amino_type.iter()).finish()
+    }
+}
+
+pub fn coin_to_amino(coin: &Coin) -> Value {
+    json!({ "denom": coin.denom, "amount": coin.amount })
+}
+
+pub fn coins_to_amino(coins: &[Coin]) -> Value {
+    coins.iter().map(coin_to_amino).collect()
+}
+
+pub fn coin_from_amino(value: &Value) -> Result<Coin, Error> {
+    Ok(Coin {
+        denom: string(value, "denom")?,
+        amount: string(value, "amount")?,
+    })
+}
+
+/// Reads a list of coins, treating a missing field as empty.
+pub fn coins_from_amino(value: &Value, key: &str) -> Result<Vec<Coin>, Error> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(coins)) => coins.iter().map(coin_from_amino).collect(),
+        Some(_) => Err(Error::Tx(format!("`{key}` is not a list of coins"))),
+    }
+}
+
+pub fn field<'a>(value: &'a Value, key: &str) -> Result<&'a Value, Error> {
+    value
+        .get(key)
+        .ok_or_else(|| Error::Tx(format!("missing field `{key}`")))
+}
+
+pub fn string(value: &Value, key: &str) -> Result<String, Error> {
+    field(value, key)?
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| Error::Tx(format!("`{key}` is not a string")))
+}
+
+/// Reads a string that amino omits when empty.
+pub fn optional_string(value: &Value, key: &str) -> Result<String, Error> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(String::new()),
+        Some(_) => string(value, key),
+    }
+}
+
+/// Reads an integer, which amino encodes as a string.
+pub fn number<T: std::str::FromStr>(value: &Value, key: &str) -> Result<T, Error> {
+    let number = match field(value, key)? {
+        Value::String(number) => number.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return Err(Error::Tx(format!("`{key}` is not a number"))),
+    };
+    number
+        .parse()
+        .map_err(|_| Error::Tx(format!("`{key}` is not a number")))
+}
+
+/// Reads an integer that amino omits when zero.
+pub fn optional_number<T: std::str::FromStr + Default>(
+    value: &Value,
+    key: &str,
+) -> Result<T, Error> {
+    match value.get(key) {
+        None | Some(Value::Null) => Ok(T::default()),
+        Some(_) => number(value, key),
+    }
+}
+
+fn address_bytes(value: &Value, key: &str) -> Result<Vec<u8>, Error> {
+    Ok(address::decode(&string(value, key)?)?.1)
+}
+
+fn base64_bytes(value: &Value, key: &str) -> Result<Vec<u8>, Error> {
+    BASE64_STANDARD
+        .decode(string(value, key)?)
+        .map_err(|e| Error::Tx(format!("`{key}` is not base64: {e}")))
+}
+
+fn json_from_bytes(bytes: &[u8]) -> Result<Value, Error> {
+    serde_json::from_slice(bytes).map_err(|e| Error::Tx(format!("invalid contract msg: {e}")))
+}
+
+fn json_to_bytes(value: &Value) -> Result<Vec<u8>, Error> {
+    serde_json::to_vec(value).map_err(|e| Error::Tx(e.to_string()))
+}
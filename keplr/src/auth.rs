@@ -0,0 +1,68 @@
+//! Account number and sequence lookup, needed for every sign doc.
+
+use crate::{lcd::Lcd, Error};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The parts of an on-chain account needed for signing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Account {
+    /// Protobuf type of the account, e.g. `/cosmos.auth.v1beta1.BaseAccount`.
+    pub type_url: String,
+    pub address: String,
+    pub account_number: u64,
+    pub sequence: u64,
+}
+
+#[derive(Deserialize)]
+struct AccountResponse {
+    account: Value,
+}
+
+#[derive(Deserialize)]
+struct BaseAccount {
+    address: String,
+    account_number: String,
+    sequence: String,
+}
+
+/// Fetches the account at `address`, e.g. a [`Key::bech32_address`](crate::Key::bech32_address).
+///
+/// Besides `BaseAccount`, accounts that wrap one are understood, like vesting, module and
+/// Ethermint accounts. Fails if the account doesn't exist yet, i.e. never received funds.
+pub async fn get_account(lcd: &Lcd, address: &str) -> Result<Account, Error> {
+    let response: AccountResponse = lcd
+        .get(&format!("/cosmos/auth/v1beta1/accounts/{address}"))
+        .await?;
+    let type_url = response.account["@type"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let base_account = find_base_account(&response.account)
+        .ok_or_else(|| Error::Serialization(format!("unsupported account type: {type_url}")))?;
+    let base_account =
+        BaseAccount::deserialize(base_account).map_err(|e| Error::Serialization(e.to_string()))?;
+    let parse = |number: &str| {
+        number
+            .parse()
+            .map_err(|_| Error::Serialization(format!("invalid number: {number}")))
+    };
+    Ok(Account {
+        type_url,
+        address: base_account.address,
+        account_number: parse(&base_account.account_number)?,
+        sequence: parse(&base_account.sequence)?,
+    })
+}
+
+/// Digs the `BaseAccount` out of accounts that embed one, e.g.
+/// `ContinuousVestingAccount.base_vesting_account.base_account` or `EthAccount.base_account`.
+fn find_base_account(account: &Value) -> Option<&Value> {
+    if account.get("account_number").is_some() {
+        return Some(account);
+    }
+    ["base_account", "base_vesting_account"]
+        .into_iter()
+        .find_map(|key| account.get(key))
+        .and_then(find_base_account)
+}
@@ -0,0 +1,266 @@
+//! Waiting for a broadcast transaction to be included in a block.
+//!
+//! [`Keplr::send_tx`](crate::Keplr::send_tx) returns as soon as the node accepted the
+//! transaction, with only its hash. [`wait_for_tx`] polls an LCD or Tendermint RPC endpoint
+//! until the result is known.
+
+use crate::{
+    lcd::{Lcd, Polled},
+    rpc::Rpc,
+    Error,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use futures::{future::Either, pin_mut};
+use futures_timer::Delay;
+use serde::{de, Deserialize, Deserializer};
+use std::{fmt::Write as _, time::Duration};
+
+const INITIAL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The result of an included transaction.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TxResponse {
+    #[serde(deserialize_with = "number")]
+    pub height: u64,
+    pub txhash: String,
+    #[serde(default)]
+    pub codespace: String,
+    /// `0` on success.
+    #[serde(default)]
+    pub code: u32,
+    /// Hex encoded result data.
+    #[serde(default)]
+    pub data: String,
+    #[serde(default)]
+    pub raw_log: String,
+    /// Per-message logs. Empty since Cosmos SDK v0.50, use [`events`](Self::events) instead.
+    #[serde(default)]
+    pub logs: Vec<TxLog>,
+    #[serde(deserialize_with = "number")]
+    pub gas_wanted: u64,
+    #[serde(deserialize_with = "number")]
+    pub gas_used: u64,
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+impl TxResponse {
+    pub fn is_success(&self) -> bool {
+        self.code == 0
+    }
+
+    /// Returns the first value of `key` in an event of type `event_type`, e.g. the contract
+    /// address in `instantiate` / `_contract_address`.
+    pub fn find_attribute(&self, event_type: &str, key: &str) -> Option<&str> {
+        self.events
+            .iter()
+            .chain(self.logs.iter().flat_map(|log| &log.events))
+            .filter(|event| event.type_ == event_type)
+            .flat_map(|event| &event.attributes)
+            .find(|attribute| attribute.key == key)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct TxLog {
+    #[serde(default, deserialize_with = "number")]
+    pub msg_index: u64,
+    #[serde(default)]
+    pub log: String,
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Event {
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub attributes: Vec<EventAttribute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct EventAttribute {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+struct GetTxResponse {
+    tx_response: TxResponse,
+}
+
+/// The result of the RPC's `/tx` route.
+#[derive(Deserialize)]
+struct RpcTx {
+    hash: String,
+    #[serde(deserialize_with = "number")]
+    height: u64,
+    tx_result: RpcTxResult,
+}
+
+#[derive(Deserialize)]
+struct RpcTxResult {
+    #[serde(default)]
+    code: u32,
+    #[serde(default)]
+    codespace: String,
+    /// Base64 encoded, unlike the LCD's hex.
+    #[serde(default)]
+    data: Option<String>,
+    #[serde(default)]
+    log: String,
+    #[serde(deserialize_with = "number")]
+    gas_wanted: u64,
+    #[serde(deserialize_with = "number")]
+    gas_used: u64,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+impl TryFrom<RpcTx> for TxResponse {
+    type Error = Error;
+
+    fn try_from(tx: RpcTx) -> Result<Self, Self::Error> {
+        let result = tx.tx_result;
+        let data = BASE64_STANDARD
+            .decode(result.data.unwrap_or_default())
+            .map_err(|e| Error::Serialization(e.to_string()))?;
+        Ok(TxResponse {
+            height: tx.height,
+            txhash: tx.hash,
+            codespace: result.codespace,
+            code: result.code,
+            // hex, like the LCD
+            data: tx_hash(&data),
+            raw_log: result.log,
+            logs: Vec::new(),
+            gas_wanted: result.gas_wanted,
+            gas_used: result.gas_used,
+            // not part of the RPC result, the block has it
+            timestamp: String::new(),
+            events: result.events,
+        })
+    }
+}
+
+/// Where [`wait_for_tx`] looks for the transaction.
+#[derive(Clone, Copy, Debug)]
+pub enum Endpoint<'a> {
+    /// `/cosmos/tx/v1beta1/txs/{hash}`
+    Lcd(&'a Lcd),
+    /// `/tx?hash=0x{hash}`. Event attributes are read as plain strings, as CometBFT v0.37 and
+    /// later return them; Tendermint v0.34 base64 encodes them.
+    Rpc(&'a Rpc),
+}
+
+impl<'a> From<&'a Lcd> for Endpoint<'a> {
+    fn from(lcd: &'a Lcd) -> Self {
+        Endpoint::Lcd(lcd)
+    }
+}
+
+impl<'a> From<&'a Rpc> for Endpoint<'a> {
+    fn from(rpc: &'a Rpc) -> Self {
+        Endpoint::Rpc(rpc)
+    }
+}
+
+impl Endpoint<'_> {
+    async fn poll_tx(self, hash: &str) -> Result<Polled<TxResponse>, Error> {
+        Ok(match self {
+            Endpoint::Lcd(lcd) => {
+                match lcd
+                    .poll::<GetTxResponse>(&format!("/cosmos/tx/v1beta1/txs/{hash}"))
+                    .await?
+                {
+                    Polled::Ready(response) => Polled::Ready(response.tx_response),
+                    Polled::Pending => Polled::Pending,
+                    Polled::Unavailable(error) => Polled::Unavailable(error),
+                }
+            }
+            Endpoint::Rpc(rpc) => match rpc.poll::<RpcTx>(&format!("/tx?hash=0x{hash}")).await? {
+                Polled::Ready(tx) => Polled::Ready(tx.try_into()?),
+                Polled::Pending => Polled::Pending,
+                Polled::Unavailable(error) => Polled::Unavailable(error),
+            },
+        })
+    }
+}
+
+/// Formats a hash returned by [`Keplr::send_tx`](crate::Keplr::send_tx) the way explorers and
+/// LCD endpoints do, as uppercase hex.
+pub fn tx_hash(hash: &[u8]) -> String {
+    hash.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02X}");
+        hex
+    })
+}
+
+/// Polls `endpoint`, an [`Lcd`] or [`Rpc`], for the transaction with `hash` until it's included
+/// in a block, backing off between requests. Network errors and server errors are retried too, nodes behind load balancers
+/// often fail a few requests.
+///
+/// Fails with [`Error::TxFailed`] if the transaction was included but failed, and with
+/// [`Error::Timeout`] if it wasn't found within `timeout`.
+pub async fn wait_for_tx<'a>(
+    endpoint: impl Into<Endpoint<'a>>,
+    hash: &str,
+    timeout: Duration,
+) -> Result<TxResponse, Error> {
+    let poll = poll_tx(endpoint.into(), hash);
+    let deadline = Delay::new(timeout);
+    pin_mut!(poll);
+    let response = match futures::future::select(poll, deadline).await {
+        Either::Left((response, _)) => response?,
+        Either::Right(_) => {
+            return Err(Error::Timeout(format!(
+                "transaction {hash} not found after {timeout:?}"
+            )))
+        }
+    };
+    if !response.is_success() {
+        return Err(Error::TxFailed {
+            hash: response.txhash,
+            codespace: response.codespace,
+            code: response.code,
+            log: response.raw_log,
+        });
+    }
+    Ok(response)
+}
+
+async fn poll_tx(endpoint: Endpoint<'_>, hash: &str) -> Result<TxResponse, Error> {
+    let mut interval = INITIAL_POLL_INTERVAL;
+    loop {
+        match endpoint.poll_tx(hash).await? {
+            Polled::Ready(response) => return Ok(response),
+            Polled::Pending => {}
+            Polled::Unavailable(error) => {
+                tracing::debug!(%error, "polling for transaction {hash} failed, retrying");
+            }
+        }
+        Delay::new(interval).await;
+        interval = (interval * 3 / 2).min(MAX_POLL_INTERVAL);
+    }
+}
+
+/// The LCD and RPC encode 64 bit integers as strings, but not every chain does so consistently.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Number {
+        String(String),
+        Number(u64),
+    }
+
+    match Number::deserialize(deserializer)? {
+        Number::String(number) => number.parse().map_err(de::Error::custom),
+        Number::Number(number) => Ok(number),
+    }
+}
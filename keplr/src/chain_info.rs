@@ -0,0 +1,311 @@
+//! Building a [`SuggestingChainInfo`] step by step, with validation:
+//!
+//! ```ignore
+//! let scrt = Currency::new("SCRT", "uscrt", 6);
+//! let chain_info = ChainInfoBuilder::new("secretdev-1")
+//!     .chain_name("LocalSecret")
+//!     .rpc("http://localhost:26657")
+//!     .rest("http://localhost:1317")
+//!     .bip44(529)
+//!     .bech32_prefix("secret")
+//!     .stake_currency(scrt.clone())
+//!     .fee_currency(scrt, GasPriceStep { low: 0.1, average: 0.25, high: 0.5 })
+//!     .build()?;
+//! ```
+
+use crate::suggest_chain_types::{
+    Bech32Config, Bip44, Currency, FeeCurrency, GasPriceStep, SuggestingChainInfo,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The most decimals Keplr supports for a currency.
+pub const MAX_DECIMALS: u8 = 18;
+
+/// Cosmos Hub's coin type, which most chains use.
+const DEFAULT_COIN_TYPE: u32 = 118;
+
+/// A problem with the chain info passed to a [`ChainInfoBuilder`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ChainInfoError {
+    #[error("{0} is required")]
+    Missing(&'static str),
+
+    #[error("{field} is not a valid http(s) URL: {url:?}")]
+    InvalidUrl { field: &'static str, url: String },
+
+    #[error("{denom} has {decimals} decimals, at most {MAX_DECIMALS} are supported")]
+    InvalidDecimals { denom: String, decimals: u8 },
+
+    #[error("{0} is listed more than once")]
+    DuplicateDenom(String),
+
+    #[error("{0} is listed with different decimals")]
+    ConflictingDecimals(String),
+
+    #[error("at least one fee currency is required")]
+    MissingFeeCurrency,
+
+    #[error("gas prices of {0} must be non-negative and ascending")]
+    InvalidGasPriceStep(String),
+}
+
+/// Builds a [`SuggestingChainInfo`], see the [module docs](self).
+///
+/// The stake and fee currencies are added to the currencies if they aren't listed already.
+/// [`build`](Self::build) reports every problem at once instead of stopping at the first.
+#[derive(Debug, Clone)]
+pub struct ChainInfoBuilder {
+    chain_id: String,
+    chain_name: Option<String>,
+    rpc: Option<String>,
+    rest: Option<String>,
+    coin_type: u32,
+    bech32_prefix: Option<String>,
+    currencies: Vec<Currency>,
+    fee_currencies: Vec<FeeCurrency>,
+    stake_currency: Option<Currency>,
+}
+
+impl ChainInfoBuilder {
+    pub fn new(chain_id: impl Into<String>) -> Self {
+        Self {
+            chain_id: chain_id.into(),
+            chain_name: None,
+            rpc: None,
+            rest: None,
+            coin_type: DEFAULT_COIN_TYPE,
+            bech32_prefix: None,
+            currencies: Vec::new(),
+            fee_currencies: Vec::new(),
+            stake_currency: None,
+        }
+    }
+
+    /// Name shown in the wallet, the chain id by default.
+    pub fn chain_name(mut self, chain_name: impl Into<String>) -> Self {
+        self.chain_name = Some(chain_name.into());
+        self
+    }
+
+    pub fn rpc(mut self, rpc: impl Into<String>) -> Self {
+        self.rpc = Some(rpc.into());
+        self
+    }
+
+    pub fn rest(mut self, rest: impl Into<String>) -> Self {
+        self.rest = Some(rest.into());
+        self
+    }
+
+    /// The BIP-44 coin type, 118 by default.
+    pub fn bip44(mut self, coin_type: u32) -> Self {
+        self.coin_type = coin_type;
+        self
+    }
+
+    /// The account prefix, the others are derived from it, see [`Bech32Config::from_prefix`].
+    pub fn bech32_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.bech32_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currencies.push(currency);
+        self
+    }
+
+    pub fn fee_currency(mut self, currency: Currency, gas_price_step: GasPriceStep) -> Self {
+        self.fee_currencies
+            .push(FeeCurrency::new(currency, gas_price_step));
+        self
+    }
+
+    pub fn stake_currency(mut self, currency: Currency) -> Self {
+        self.stake_currency = Some(currency);
+        self
+    }
+
+    /// Validates the chain info and returns it, or all problems found.
+    pub fn build(self) -> Result<SuggestingChainInfo, Vec<ChainInfoError>> {
+        let mut errors = Vec::new();
+
+        if self.chain_id.trim().is_empty() {
+            errors.push(ChainInfoError::Missing("chain_id"));
+        }
+
+        let mut url = |field, url: Option<String>| match url {
+            Some(url) if is_http_url(&url) => url,
+            Some(url) => {
+                errors.push(ChainInfoError::InvalidUrl { field, url });
+                String::new()
+            }
+            None => {
+                errors.push(ChainInfoError::Missing(field));
+                String::new()
+            }
+        };
+        let rpc = url("rpc", self.rpc);
+        let rest = url("rest", self.rest);
+
+        let bech32_prefix = self.bech32_prefix.unwrap_or_default();
+        if bech32_prefix.is_empty() {
+            errors.push(ChainInfoError::Missing("bech32_prefix"));
+        }
+
+        check_denoms(
+            self.currencies
+                .iter()
+                .map(|currency| (currency.coin_minimal_denom.as_str(), currency.coin_decimals)),
+            &mut errors,
+        );
+        check_denoms(
+            self.fee_currencies
+                .iter()
+                .map(|currency| (currency.coin_minimal_denom.as_str(), currency.coin_decimals)),
+            &mut errors,
+        );
+        if self.fee_currencies.is_empty() {
+            errors.push(ChainInfoError::MissingFeeCurrency);
+        }
+        for currency in &self.fee_currencies {
+            let GasPriceStep { low, average, high } = currency.gas_price_step;
+            if !(0.0 <= low && low <= average && average <= high) {
+                errors.push(ChainInfoError::InvalidGasPriceStep(
+                    currency.coin_minimal_denom.clone(),
+                ));
+            }
+        }
+        check_decimals(
+            self.currencies
+                .iter()
+                .map(|currency| (currency.coin_minimal_denom.as_str(), currency.coin_decimals))
+                .chain(
+                    self.fee_currencies.iter().map(|currency| {
+                        (currency.coin_minimal_denom.as_str(), currency.coin_decimals)
+                    }),
+                )
+                .chain(self.stake_currency.iter().map(|currency| {
+                    (currency.coin_minimal_denom.as_str(), currency.coin_decimals)
+                })),
+            &mut errors,
+        );
+        let stake_currency = match self.stake_currency {
+            Some(currency) => {
+                // listed currencies were checked above
+                let listed = self
+                    .currencies
+                    .iter()
+                    .any(|listed| listed.coin_minimal_denom == currency.coin_minimal_denom);
+                if !listed {
+                    check_denoms(
+                        [(currency.coin_minimal_denom.as_str(), currency.coin_decimals)],
+                        &mut errors,
+                    );
+                }
+                currency
+            }
+            None => {
+                errors.push(ChainInfoError::Missing("stake_currency"));
+                Currency::new("", "", 0)
+            }
+        };
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let mut currencies = self.currencies;
+        let implied = std::iter::once(stake_currency.clone())
+            .chain(self.fee_currencies.iter().map(FeeCurrency::currency));
+        for currency in implied {
+            if !currencies
+                .iter()
+                .any(|listed| listed.coin_minimal_denom == currency.coin_minimal_denom)
+            {
+                currencies.push(currency);
+            }
+        }
+
+        Ok(SuggestingChainInfo {
+            chain_name: self.chain_name.unwrap_or_else(|| self.chain_id.clone()),
+            chain_id: self.chain_id,
+            rpc,
+            rest,
+            bip44: Bip44 {
+                coin_type: self.coin_type,
+            },
+            bech32_config: Bech32Config::from_prefix(&bech32_prefix),
+            currencies,
+            fee_currencies: self.fee_currencies,
+            stake_currency,
+        })
+    }
+}
+
+/// Reports denoms listed twice and decimals Keplr can't handle.
+fn check_denoms<'a>(
+    currencies: impl IntoIterator<Item = (&'a str, u8)>,
+    errors: &mut Vec<ChainInfoError>,
+) {
+    let mut seen = BTreeSet::new();
+    for (denom, decimals) in currencies {
+        if !seen.insert(denom) {
+            errors.push(ChainInfoError::DuplicateDenom(denom.to_string()));
+        }
+        if decimals > MAX_DECIMALS {
+            errors.push(ChainInfoError::InvalidDecimals {
+                denom: denom.to_string(),
+                decimals,
+            });
+        }
+    }
+}
+
+/// Reports denoms whose decimals differ between the currency lists, once per denom.
+fn check_decimals<'a>(
+    currencies: impl IntoIterator<Item = (&'a str, u8)>,
+    errors: &mut Vec<ChainInfoError>,
+) {
+    let mut first = BTreeMap::new();
+    let mut conflicting = BTreeSet::new();
+    for (denom, decimals) in currencies {
+        if *first.entry(denom).or_insert(decimals) != decimals && conflicting.insert(denom) {
+            errors.push(ChainInfoError::ConflictingDecimals(denom.to_string()));
+        }
+    }
+}
+
+/// `http(s)://` followed by a host, optionally with a port and path.
+fn is_http_url(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    // IPv6 hosts are bracketed, as they contain colons themselves
+    let (host, port) = match authority.split_once(']') {
+        Some((host, "")) => (host, None),
+        Some((host, port)) => match port.strip_prefix(':') {
+            Some(port) => (host, Some(port)),
+            None => return false,
+        },
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let valid_host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.parse::<std::net::Ipv6Addr>().is_ok(),
+        None => {
+            !host.is_empty()
+                && host
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+        }
+    };
+    valid_host
+        && port.is_none_or(|port| port.parse::<u16>().is_ok())
+        && !url.chars().any(char::is_whitespace)
+}
@@ -0,0 +1,256 @@
+//! Chain info from the [cosmos/chain-registry](https://github.com/cosmos/chain-registry).
+//!
+//! Turns a chain's `chain.json` and `assetlist.json` into a [`SuggestingChainInfo`], e.g. from
+//! snapshots embedded in the app:
+//!
+//! ```ignore
+//! let chain_info = keplr::chain_registry::from_json(
+//!     include_str!("secretnetwork/chain.json"),
+//!     include_str!("secretnetwork/assetlist.json"),
+//! )?;
+//! keplr.suggest_chain(&chain_info).await?;
+//! ```
+//!
+//! Native builds can also read them from a checkout of the registry with [`from_dir`].
+//!
+//! Only the fields needed for a suggestion are read, everything else is ignored.
+
+use crate::{
+    suggest_chain_types::{
+        Bech32Config, Bip44, Currency, FeeCurrency, GasPriceStep, SuggestingChainInfo,
+    },
+    Error,
+};
+use serde::{Deserialize, Serialize};
+
+/// Gas prices used by Keplr for fee tokens that don't list their own.
+const DEFAULT_GAS_PRICE_STEP: GasPriceStep = GasPriceStep {
+    low: 0.01,
+    average: 0.025,
+    high: 0.04,
+};
+
+/// The parts of a `chain.json` used for suggestions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Chain {
+    pub chain_name: String,
+    pub chain_id: String,
+    #[serde(default)]
+    pub pretty_name: Option<String>,
+    pub bech32_prefix: String,
+    pub slip44: u32,
+    #[serde(default)]
+    pub fees: Fees,
+    #[serde(default)]
+    pub staking: Staking,
+    #[serde(default)]
+    pub apis: Apis,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fees {
+    #[serde(default)]
+    pub fee_tokens: Vec<FeeToken>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeToken {
+    pub denom: String,
+    #[serde(default)]
+    pub fixed_min_gas_price: Option<f64>,
+    #[serde(default)]
+    pub low_gas_price: Option<f64>,
+    #[serde(default)]
+    pub average_gas_price: Option<f64>,
+    #[serde(default)]
+    pub high_gas_price: Option<f64>,
+}
+
+impl FeeToken {
+    /// The listed gas prices, filling in missing ones from the minimum price or Keplr's
+    /// defaults, while keeping them in ascending order.
+    pub fn gas_price_step(&self) -> GasPriceStep {
+        let low = self
+            .low_gas_price
+            .or(self.fixed_min_gas_price)
+            .unwrap_or(DEFAULT_GAS_PRICE_STEP.low);
+        let average = self
+            .average_gas_price
+            .unwrap_or(DEFAULT_GAS_PRICE_STEP.average.max(low));
+        let high = self
+            .high_gas_price
+            .unwrap_or(DEFAULT_GAS_PRICE_STEP.high.max(average));
+        GasPriceStep { low, average, high }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Staking {
+    #[serde(default)]
+    pub staking_tokens: Vec<StakingToken>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StakingToken {
+    pub denom: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Apis {
+    #[serde(default)]
+    pub rpc: Vec<Endpoint>,
+    #[serde(default)]
+    pub rest: Vec<Endpoint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub address: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+/// The parts of an `assetlist.json` used for suggestions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetList {
+    pub chain_name: String,
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    pub base: String,
+    pub display: String,
+    pub symbol: String,
+    pub denom_units: Vec<DenomUnit>,
+    #[serde(default)]
+    pub coingecko_id: Option<String>,
+    /// `sdk.coin`, `ics20`, `cw20`, `snip20`, ...
+    #[serde(default)]
+    pub type_asset: Option<String>,
+}
+
+impl Asset {
+    /// The exponent of the display unit, e.g. 6 for `scrt` on `uscrt`.
+    pub fn decimals(&self) -> Option<u8> {
+        self.denom_units
+            .iter()
+            .find(|unit| unit.denom == self.display)
+            .map(|unit| unit.exponent)
+    }
+
+    /// Whether this is a bank coin, as opposed to a token held by a contract.
+    pub fn is_native(&self) -> bool {
+        matches!(
+            self.type_asset.as_deref(),
+            None | Some("sdk.coin" | "ics20")
+        )
+    }
+
+    fn currency(&self) -> Result<Currency, Error> {
+        let coin_decimals = self.decimals().ok_or_else(|| {
+            Error::ChainRegistry(format!(
+                "{} has no denom unit for its display denom {}",
+                self.base, self.display
+            ))
+        })?;
+        Ok(Currency {
+            coin_gecko_id: self.coingecko_id.clone().unwrap_or_default(),
+            ..Currency::new(&self.symbol, &self.base, coin_decimals)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DenomUnit {
+    pub denom: String,
+    pub exponent: u8,
+}
+
+/// Parses `chain.json` and `assetlist.json` and converts them with [`to_chain_info`].
+pub fn from_json(chain_json: &str, assetlist_json: &str) -> Result<SuggestingChainInfo, Error> {
+    let chain: Chain = serde_json::from_str(chain_json)
+        .map_err(|e| Error::ChainRegistry(format!("invalid chain.json: {e}")))?;
+    let assets: AssetList = serde_json::from_str(assetlist_json)
+        .map_err(|e| Error::ChainRegistry(format!("invalid assetlist.json: {e}")))?;
+    to_chain_info(&chain, &assets)
+}
+
+/// Reads `chain.json` and `assetlist.json` from a chain's directory in a checkout of the
+/// registry, e.g. `chain-registry/secretnetwork`, and converts them with [`from_json`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn from_dir(path: impl AsRef<std::path::Path>) -> Result<SuggestingChainInfo, Error> {
+    let read = |file: &str| {
+        let path = path.as_ref().join(file);
+        std::fs::read_to_string(&path)
+            .map_err(|e| Error::ChainRegistry(format!("failed to read {}: {e}", path.display())))
+    };
+    from_json(&read("chain.json")?, &read("assetlist.json")?)
+}
+
+/// Builds the suggestion for `chain`, using its first RPC and REST endpoints and the native
+/// assets as currencies.
+pub fn to_chain_info(chain: &Chain, assets: &AssetList) -> Result<SuggestingChainInfo, Error> {
+    if chain.chain_name != assets.chain_name {
+        return Err(Error::ChainRegistry(format!(
+            "the asset list is for {}, not {}",
+            assets.chain_name, chain.chain_name
+        )));
+    }
+    let endpoint = |endpoints: &[Endpoint], kind: &str| {
+        endpoints
+            .first()
+            .map(|endpoint| endpoint.address.clone())
+            .ok_or_else(|| {
+                Error::ChainRegistry(format!("{} lists no {kind} endpoint", chain.chain_id))
+            })
+    };
+    let asset = |denom: &str| {
+        assets
+            .assets
+            .iter()
+            .find(|asset| asset.base == denom)
+            .ok_or_else(|| Error::ChainRegistry(format!("{denom} is missing from the asset list")))
+    };
+
+    let fee_currencies = chain
+        .fees
+        .fee_tokens
+        .iter()
+        .map(|token| {
+            let currency = asset(&token.denom)?.currency()?;
+            Ok(FeeCurrency::new(currency, token.gas_price_step()))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    if fee_currencies.is_empty() {
+        return Err(Error::ChainRegistry(format!(
+            "{} lists no fee tokens",
+            chain.chain_id
+        )));
+    }
+    let staking_token = chain.staking.staking_tokens.first().ok_or_else(|| {
+        Error::ChainRegistry(format!("{} lists no staking token", chain.chain_id))
+    })?;
+
+    Ok(SuggestingChainInfo {
+        chain_id: chain.chain_id.clone(),
+        chain_name: chain
+            .pretty_name
+            .clone()
+            .unwrap_or_else(|| chain.chain_name.clone()),
+        rpc: endpoint(&chain.apis.rpc, "RPC")?,
+        rest: endpoint(&chain.apis.rest, "REST")?,
+        bip44: Bip44 {
+            coin_type: chain.slip44,
+        },
+        bech32_config: Bech32Config::from_prefix(&chain.bech32_prefix),
+        currencies: assets
+            .assets
+            .iter()
+            .filter(|asset| asset.is_native())
+            .map(Asset::currency)
+            .collect::<Result<_, _>>()?,
+        fee_currencies,
+        stake_currency: asset(&staking_token.denom)?.currency()?,
+    })
+}
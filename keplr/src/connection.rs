@@ -0,0 +1,189 @@
+//! Framework-agnostic connection state, shared by the Yew, Dioxus and Leptos integrations.
+//!
+//! The hooks keep a [`ConnectionState`], run [`connect`], [`disconnect`] or [`refresh`] in the
+//! background and [`apply`](ConnectionState::apply) the resulting [`ConnectionAction`].
+//! [`Connections`] does the same for several chains at once. [`attach_on_load`] hands them the
+//! wallet handle, also when the extension injects it after the page started.
+
+use crate::{events::EventListener, provider::WalletProvider, Error, Keplr, Key};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    #[default]
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectionState {
+    pub status: ConnectionStatus,
+    /// The current key while connected.
+    pub key: Option<Key>,
+    /// The last error, cleared by the next connection attempt.
+    pub error: Option<Error>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectionAction {
+    Connect,
+    Connected(Key),
+    /// The user switched accounts.
+    KeyChanged(Key),
+    Disconnected,
+    Failed(Error),
+}
+
+impl ConnectionState {
+    pub fn is_connected(&self) -> bool {
+        self.status == ConnectionStatus::Connected
+    }
+
+    /// Returns the state after `action`.
+    pub fn apply(&self, action: ConnectionAction) -> Self {
+        match action {
+            ConnectionAction::Connect => Self {
+                status: ConnectionStatus::Connecting,
+                key: self.key.clone(),
+                error: None,
+            },
+            ConnectionAction::Connected(key) => Self {
+                status: ConnectionStatus::Connected,
+                key: Some(key),
+                error: None,
+            },
+            // a refresh that finishes after disconnecting must not reconnect
+            ConnectionAction::KeyChanged(key) if self.is_connected() => Self {
+                key: Some(key),
+                ..self.clone()
+            },
+            ConnectionAction::KeyChanged(_) => self.clone(),
+            ConnectionAction::Disconnected => Self::default(),
+            ConnectionAction::Failed(error) => Self {
+                status: match self.status {
+                    ConnectionStatus::Connecting => ConnectionStatus::Disconnected,
+                    status => status,
+                },
+                key: self.key.clone(),
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// The [`ConnectionState`] of each chain, for apps connected to several chains at once.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Connections {
+    /// Chains in their default, disconnected state without an error aren't stored.
+    states: BTreeMap<String, ConnectionState>,
+}
+
+impl Connections {
+    /// The state of `chain_id`, disconnected if it was never connected.
+    pub fn state(&self, chain_id: &str) -> ConnectionState {
+        self.states.get(chain_id).cloned().unwrap_or_default()
+    }
+
+    pub fn key(&self, chain_id: &str) -> Option<&Key> {
+        self.states.get(chain_id)?.key.as_ref()
+    }
+
+    /// The chains that are currently connected.
+    pub fn connected(&self) -> impl Iterator<Item = &str> {
+        self.states
+            .iter()
+            .filter(|(_, state)| state.is_connected())
+            .map(|(chain_id, _)| chain_id.as_str())
+    }
+
+    /// Applies `action` to the state of `chain_id` only.
+    pub fn apply(&mut self, chain_id: &str, action: ConnectionAction) {
+        let state = self.state(chain_id).apply(action);
+        if state == ConnectionState::default() {
+            self.states.remove(chain_id);
+        } else {
+            self.states.insert(chain_id.to_string(), state);
+        }
+    }
+}
+
+/// Calls `attach` with the handle for `provider`, right away if the wallet is injected already
+/// and otherwise once the page has loaded. Dropping the returned listener stops waiting.
+pub fn attach_on_load(
+    provider: WalletProvider,
+    mut attach: impl FnMut(Keplr) + 'static,
+) -> Option<EventListener> {
+    if let Ok(keplr) = Keplr::with_provider(provider) {
+        attach(keplr);
+        return None;
+    }
+    EventListener::new("load", move |_| {
+        if let Ok(keplr) = Keplr::with_provider(provider) {
+            attach(keplr);
+        }
+    })
+    .ok()
+}
+
+/// Enables `chain_id` and fetches its key.
+pub async fn connect(keplr: Option<&Keplr>, chain_id: &str) -> ConnectionAction {
+    let result = async {
+        let keplr = keplr.ok_or(Error::KeplrUnavailable)?;
+        keplr.enable(vec![chain_id.to_string()]).await?;
+        keplr.get_key(chain_id).await
+    };
+    match result.await {
+        Ok(key) => ConnectionAction::Connected(key),
+        Err(error) => ConnectionAction::Failed(error),
+    }
+}
+
+/// Enables all of `chain_ids` with a single request and fetches each key. A chain whose key
+/// can't be fetched doesn't keep the others from connecting.
+pub async fn connect_all(
+    keplr: Option<&Keplr>,
+    chain_ids: &[String],
+) -> Vec<(String, ConnectionAction)> {
+    let enabled = match keplr {
+        Some(keplr) => keplr.enable(chain_ids.to_vec()).await.map(|()| keplr),
+        None => Err(Error::KeplrUnavailable),
+    };
+    let keplr = match enabled {
+        Ok(keplr) => keplr,
+        Err(error) => {
+            return chain_ids
+                .iter()
+                .map(|chain_id| (chain_id.clone(), ConnectionAction::Failed(error.clone())))
+                .collect()
+        }
+    };
+    let mut actions = Vec::with_capacity(chain_ids.len());
+    for chain_id in chain_ids {
+        let action = match keplr.get_key(chain_id).await {
+            Ok(key) => ConnectionAction::Connected(key),
+            Err(error) => ConnectionAction::Failed(error),
+        };
+        actions.push((chain_id.clone(), action));
+    }
+    actions
+}
+
+/// Disables `chain_id`.
+pub async fn disconnect(keplr: Option<&Keplr>, chain_id: &str) -> ConnectionAction {
+    let Some(keplr) = keplr else {
+        return ConnectionAction::Disconnected;
+    };
+    match keplr.disable(chain_id).await {
+        Ok(()) => ConnectionAction::Disconnected,
+        Err(error) => ConnectionAction::Failed(error),
+    }
+}
+
+/// Fetches the key for `chain_id` again, after the user switched accounts.
+pub async fn refresh(keplr: &Keplr, chain_id: &str) -> ConnectionAction {
+    match keplr.get_key(chain_id).await {
+        Ok(key) => ConnectionAction::KeyChanged(key),
+        Err(error) => ConnectionAction::Failed(error),
+    }
+}
@@ -0,0 +1,174 @@
+//! Conversions between this crate's types and [`cosmrs`] types.
+
+use crate::{
+    sign_types::{Coin, DirectSignResponse, SignDoc, StdFee},
+    AccountData, Algo, Error, KeplrOfflineSigner,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use cosmrs::{
+    crypto::PublicKey,
+    proto::cosmos::tx::v1beta1::TxRaw,
+    tx::{self, AuthInfo, Body, Fee, Raw},
+    AccountId,
+};
+
+impl From<cosmrs::ErrorReport> for Error {
+    fn from(error: cosmrs::ErrorReport) -> Self {
+        Error::Tx(error.to_string())
+    }
+}
+
+impl TryFrom<&Coin> for cosmrs::Coin {
+    type Error = Error;
+
+    fn try_from(coin: &Coin) -> Result<Self, Self::Error> {
+        Ok(Self {
+            denom: coin.denom.parse()?,
+            amount: coin
+                .amount
+                .parse()
+                .map_err(|_| Error::Tx(format!("invalid amount: {}", coin.amount)))?,
+        })
+    }
+}
+
+impl From<&cosmrs::Coin> for Coin {
+    fn from(coin: &cosmrs::Coin) -> Self {
+        Self {
+            denom: coin.denom.to_string(),
+            amount: coin.amount.to_string(),
+        }
+    }
+}
+
+impl TryFrom<&StdFee> for Fee {
+    type Error = Error;
+
+    fn try_from(fee: &StdFee) -> Result<Self, Self::Error> {
+        let account_id = |address: &Option<String>| -> Result<Option<AccountId>, Error> {
+            address
+                .as_deref()
+                .map(|address| address.parse().map_err(Into::into))
+                .transpose()
+        };
+        Ok(Self {
+            amount: fee
+                .amount
+                .iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            gas_limit: fee
+                .gas
+                .parse()
+                .map_err(|_| Error::Tx(format!("invalid gas: {}", fee.gas)))?,
+            payer: account_id(&fee.payer)?,
+            granter: account_id(&fee.granter)?,
+        })
+    }
+}
+
+impl From<&Fee> for StdFee {
+    fn from(fee: &Fee) -> Self {
+        Self {
+            amount: fee.amount.iter().map(Into::into).collect(),
+            gas: fee.gas_limit.to_string(),
+            payer: fee.payer.as_ref().map(ToString::to_string),
+            granter: fee.granter.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+impl From<tx::SignDoc> for SignDoc {
+    fn from(sign_doc: tx::SignDoc) -> Self {
+        Self {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc.account_number.to_string(),
+        }
+    }
+}
+
+impl TryFrom<SignDoc> for tx::SignDoc {
+    type Error = Error;
+
+    fn try_from(sign_doc: SignDoc) -> Result<Self, Self::Error> {
+        Ok(Self {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc
+                .account_number
+                .parse()
+                .map_err(|_| Error::Tx("invalid account number".to_string()))?,
+        })
+    }
+}
+
+/// Assembles the signed transaction. The body and auth info are taken from the signed doc, since
+/// the user may have changed the fee or memo in the popup.
+impl TryFrom<DirectSignResponse> for Raw {
+    type Error = Error;
+
+    fn try_from(response: DirectSignResponse) -> Result<Self, Self::Error> {
+        let signature = BASE64_STANDARD
+            .decode(response.signature.signature)
+            .map_err(|e| Error::Tx(e.to_string()))?;
+        Ok(TxRaw {
+            body_bytes: response.signed.body_bytes,
+            auth_info_bytes: response.signed.auth_info_bytes,
+            signatures: vec![signature],
+        }
+        .into())
+    }
+}
+
+impl AccountData {
+    /// Returns the account's public key as a [`cosmrs`] key, e.g. for building
+    /// [`SignerInfo`](cosmrs::tx::SignerInfo)s.
+    pub fn public_key(&self) -> Result<PublicKey, Error> {
+        let public_key = match self.algo {
+            Algo::Secp256k1 => cosmrs::tendermint::PublicKey::from_raw_secp256k1(&self.pubkey),
+            Algo::Ed25519 => cosmrs::tendermint::PublicKey::from_raw_ed25519(&self.pubkey),
+            algo => {
+                return Err(Error::Tx(format!(
+                    "{algo:?} public keys are not supported by cosmrs"
+                )))
+            }
+        };
+        public_key
+            .map(Into::into)
+            .ok_or_else(|| Error::Tx("invalid public key".to_string()))
+    }
+}
+
+impl KeplrOfflineSigner {
+    /// Signs a [`cosmrs`] sign doc in `DIRECT` mode and returns the signed transaction.
+    pub async fn sign_direct_tx(
+        &self,
+        signer_address: &str,
+        sign_doc: tx::SignDoc,
+    ) -> Result<Raw, Error> {
+        self.sign_direct(signer_address, &sign_doc.into())
+            .await?
+            .try_into()
+    }
+
+    /// Builds the sign doc for `body` and `auth_info` on this signer's chain, signs it and
+    /// returns the signed transaction.
+    pub async fn sign_tx(
+        &self,
+        signer_address: &str,
+        body: &Body,
+        auth_info: &AuthInfo,
+        account_number: u64,
+    ) -> Result<Raw, Error> {
+        let chain_id = self
+            .try_chain_id()
+            .ok_or_else(|| Error::Tx("the signer has no chain id".to_string()))?
+            .parse()
+            .map_err(|e: cosmrs::tendermint::Error| Error::Tx(e.to_string()))?;
+        let sign_doc = tx::SignDoc::new(body, auth_info, &chain_id, account_number)?;
+        self.sign_direct_tx(signer_address, sign_doc).await
+    }
+}
@@ -1,5 +1,5 @@
-use crate::keplr::{suggest_chain, suggest_chain_types::*, Keplr};
-use serde::{Deserialize, Serialize};
+use crate::{suggest_chain_types::*, Keplr};
+use serde::Serialize;
 use wasm_bindgen::prelude::*;
 use web_sys::console;
 
@@ -12,27 +12,23 @@ extern "C" {
 #[wasm_bindgen(start)]
 pub async fn start() -> Result<(), JsValue> {
     const CHAIN_ID: &str = "secret-4";
-    let window = web_sys::window().expect("no global `window` exists");
 
-    if js_sys::Reflect::has(&window, &JsValue::from_str("keplr")).unwrap() {
-        // suggest().await;
-        // disable(CHAIN_ID);
-        let keplr = Keplr::new().enable(CHAIN_ID).await?;
+    if let Some(keplr) = Keplr::detect() {
+        // suggest(&keplr).await;
+        // keplr.disable(CHAIN_ID).await?;
+        keplr.enable(vec![CHAIN_ID.to_string()]).await?;
         keplr.debug();
 
-        let key_info = keplr.get_key(CHAIN_ID).await;
+        let key_info = keplr.get_key(CHAIN_ID).await?;
         console::log_1(&serde_wasm_bindgen::to_value(&key_info)?);
 
-        let offline_signer = keplr.get_offline_signer(CHAIN_ID);
+        let offline_signer = keplr.get_offline_signer(CHAIN_ID)?;
 
-        let accounts = offline_signer.get_accounts().await;
-        let accounts = js_sys::Array::from(&accounts);
-        let account = accounts.get(0);
-        console::log_1(&account);
+        let accounts = offline_signer.get_accounts().await?;
+        console::log_1(&format!("{:?}", accounts[0]).into());
 
         // hey it works
 
-        // let signer_address = JsString::from_str(&key_info.bech32_address).unwrap();
         // let sign_doc = StdSignDoc {
         //     chain_id: CHAIN_ID.into(),
         //     account_number: "0".into(),
@@ -42,7 +38,7 @@ pub async fn start() -> Result<(), JsValue> {
         //     memo: "".into(),
         // };
         // let result = offline_signer
-        //     .sign_amino(signer_address, serde_wasm_bindgen::to_value(&sign_doc)?)
+        //     .sign_amino(&key_info.bech32_address, &sign_doc)
         //     .await;
 
         let enigma_utils = keplr.get_enigma_utils(CHAIN_ID);
@@ -58,15 +54,11 @@ pub async fn start() -> Result<(), JsValue> {
             value: "hello".to_string(),
         };
 
-        let message = enigma_utils
-            .encrypt(contract_code_hash.into(), &msg)
-            .await?;
+        let message = enigma_utils.encrypt(contract_code_hash, &msg).await?;
 
-        let message: Vec<u8> = serde_wasm_bindgen::from_value(message.into())?;
         let nonce = &message[0..32];
         let ciphertext = &message[64..];
         let plaintext = enigma_utils.decrypt(ciphertext, nonce).await?;
-        let plaintext = plaintext.to_vec();
         let plaintext = String::from_utf8_lossy(&plaintext);
         console::log_1(&plaintext.to_string().into());
     } else {
@@ -75,7 +67,7 @@ pub async fn start() -> Result<(), JsValue> {
     Ok(())
 }
 
-pub async fn suggest() {
+pub async fn suggest(keplr: &Keplr) {
     let chain_info = SuggestingChainInfo {
         chain_id: "mychain-1".to_string(),
         chain_name: "my new chain".to_string(),
@@ -115,6 +107,5 @@ pub async fn suggest() {
         },
     };
 
-    let chain_info_js = serde_wasm_bindgen::to_value(&chain_info).unwrap();
-    let _ = suggest_chain(chain_info_js).await;
+    let _ = keplr.suggest_chain(&chain_info).await;
 }
@@ -0,0 +1,160 @@
+//! Wallet state hooks for Dioxus apps.
+//!
+//! ```ignore
+//! #[component]
+//! fn Connect() -> Element {
+//!     let keplr = use_keplr("secret-4");
+//!     match keplr.key() {
+//!         Some(key) => rsx! { span { "{key.bech32_address}" } },
+//!         None => rsx! { button { onclick: move |_| keplr.connect(), "Connect" } },
+//!     }
+//! }
+//! ```
+
+use crate::{
+    connection::{self, ConnectionAction, ConnectionState},
+    events::EventListener,
+    provider::WalletProvider,
+    Keplr, Key,
+};
+use ::dioxus::prelude::*;
+
+/// Returned by [`use_keplr`]. Cheap to copy into event handlers.
+#[derive(Clone, Copy)]
+pub struct UseKeplr {
+    keplr: Signal<Option<Keplr>>,
+    chain_id: CopyValue<String>,
+    state: Signal<ConnectionState>,
+    listener: CopyValue<Option<EventListener>>,
+    /// Fetches the key again, callable from the keystore event outside the Dioxus runtime.
+    refresh: Callback<()>,
+}
+
+impl UseKeplr {
+    /// `None` if Keplr isn't installed, or not injected yet.
+    pub fn keplr(&self) -> Option<Keplr> {
+        self.keplr.read().clone()
+    }
+
+    /// The chain the hook was last rendered with.
+    pub fn chain_id(&self) -> String {
+        self.chain_id.read().clone()
+    }
+
+    pub fn state(&self) -> ConnectionState {
+        self.state.read().clone()
+    }
+
+    pub fn key(&self) -> Option<Key> {
+        self.state.read().key.clone()
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.state.read().is_connected()
+    }
+
+    /// Enables the chain and fetches its key.
+    pub fn connect(&self) {
+        let this = *self;
+        this.apply(ConnectionAction::Connect);
+        spawn(async move {
+            let keplr = this.keplr.peek().clone();
+            let chain_id = this.chain_id.peek().clone();
+            let action = connection::connect(keplr.as_ref(), &chain_id).await;
+            // the chain changed in the meantime
+            if *this.chain_id.peek() == chain_id {
+                this.apply(action);
+                this.follow_keystore();
+            }
+        });
+    }
+
+    /// Disables the chain.
+    pub fn disconnect(&self) {
+        let this = *self;
+        spawn(async move {
+            let keplr = this.keplr.peek().clone();
+            let chain_id = this.chain_id.peek().clone();
+            let action = connection::disconnect(keplr.as_ref(), &chain_id).await;
+            if *this.chain_id.peek() == chain_id {
+                this.apply(action);
+                this.follow_keystore();
+            }
+        });
+    }
+
+    fn apply(&self, action: ConnectionAction) {
+        let mut state = self.state;
+        let next = state.peek().apply(action);
+        state.set(next);
+    }
+
+    /// Refreshes the key on account switches while connected.
+    fn follow_keystore(&self) {
+        let mut listener = self.listener;
+        let keplr = match (self.keplr.peek().clone(), self.state.peek().is_connected()) {
+            (Some(keplr), true) => keplr,
+            _ => return listener.set(None),
+        };
+        let refresh = self.refresh;
+        listener.set(keplr.on_keystore_change(move || refresh.call(())).ok());
+    }
+
+    /// Starts over with `chain_id`, the connection to the previous chain is forgotten.
+    fn switch_chain(&self, chain_id: String) {
+        let (mut current, mut listener, mut state) = (self.chain_id, self.listener, self.state);
+        if *current.peek() != chain_id {
+            current.set(chain_id);
+            listener.set(None);
+            state.set(ConnectionState::default());
+        }
+    }
+}
+
+/// Tracks the connection to `chain_id`. The key is refreshed when the user switches accounts.
+///
+/// When `chain_id` changes, the state goes back to disconnected and the new chain has to be
+/// connected. If Keplr isn't injected on the first render, it's looked up again once the page
+/// loaded.
+pub fn use_keplr(chain_id: &str) -> UseKeplr {
+    let mut keplr = use_signal(Keplr::detect);
+    let chain_id = chain_id.to_string();
+    let current_chain_id = use_hook(|| CopyValue::new(chain_id.clone()));
+    let mut state = use_signal(ConnectionState::default);
+    let listener = use_hook(|| CopyValue::new(None));
+
+    let refresh = use_callback(move |()| {
+        spawn(async move {
+            let Some(keplr) = keplr.peek().clone() else {
+                return;
+            };
+            let chain_id = current_chain_id.peek().clone();
+            let action = connection::refresh(&keplr, &chain_id).await;
+            if *current_chain_id.peek() == chain_id {
+                let next = state.peek().apply(action);
+                state.set(next);
+            }
+        });
+    });
+    let found = use_callback(move |found: Keplr| keplr.set(Some(found)));
+    use_hook(|| {
+        // the extension may inject itself after the first render
+        let loader = match keplr.peek().is_none() {
+            true => {
+                connection::attach_on_load(WalletProvider::Keplr, move |keplr| found.call(keplr))
+            }
+            false => None,
+        };
+        CopyValue::new(loader)
+    });
+
+    let this = UseKeplr {
+        keplr,
+        chain_id: current_chain_id,
+        state,
+        listener,
+        refresh,
+    };
+    use_effect(use_reactive!(|chain_id| this.switch_chain(chain_id)));
+    this
+}
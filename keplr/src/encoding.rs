@@ -0,0 +1,117 @@
+//! Serde adapters for byte fields, and the sorted JSON used for amino signing.
+//!
+//! Keplr hands bytes over as `Uint8Array`s, while backends usually expect base64 strings in JSON.
+//! Both adapters accept either representation when deserializing (as well as `ArrayBuffer`s and
+//! plain arrays of numbers), so the same type can be read from Keplr and posted to an API.
+
+use ::base64::prelude::{Engine as _, BASE64_STANDARD};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use std::fmt;
+
+/// Serializes bytes as a base64 string.
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Foo {
+///     #[serde(with = "keplr::encoding::base64")]
+///     bytes: Vec<u8>,
+/// }
+/// ```
+pub mod base64 {
+    use super::*;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64_STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_any(BytesVisitor)
+    }
+}
+
+/// Serializes bytes as raw bytes, which `serde_wasm_bindgen` turns into a `Uint8Array`.
+pub mod uint8array {
+    use super::*;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        deserializer.deserialize_any(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Uint8Array, an array of bytes or a base64 string")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        BASE64_STANDARD.decode(v).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+
+    // `null` and `undefined` show up for keys that have no public key yet.
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Vec::new())
+    }
+}
+
+/// Serializes a value into the shape Keplr expects: maps become plain objects and bytes become
+/// `Uint8Array`s.
+pub(crate) fn to_js<T: serde::Serialize + ?Sized>(
+    value: &T,
+) -> Result<web_sys::wasm_bindgen::JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true))
+}
+
+/// Serializes a value to compact JSON with object keys sorted recursively, the form amino sign
+/// docs are signed in.
+pub fn to_sorted_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, crate::Error> {
+    let value =
+        serde_json::to_value(value).map_err(|e| crate::Error::Serialization(e.to_string()))?;
+    serde_json::to_string(&sort_keys(value)).map_err(|e| crate::Error::Serialization(e.to_string()))
+}
+
+// `serde_json::Map` only keeps keys sorted as long as nothing in the build enables
+// `preserve_order`, so sort explicitly.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
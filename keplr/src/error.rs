@@ -8,6 +8,50 @@ pub enum Error {
 
     #[error("Keplr is unavailable!")]
     KeplrUnavailable,
+
+    #[error("{0} is unavailable!")]
+    WalletUnavailable(String),
+
+    #[error("Address Error: {0}")]
+    Address(String),
+
+    #[error("Transaction Error: {0}")]
+    Tx(String),
+
+    #[error("Signature Error: {0}")]
+    Signature(String),
+
+    #[error("Request Error: {0}")]
+    Request(String),
+
+    #[error("Chain Registry Error: {0}")]
+    ChainRegistry(String),
+
+    #[error("Transaction {hash} failed with code {code} ({codespace}): {log}")]
+    TxFailed {
+        hash: String,
+        codespace: String,
+        code: u32,
+        log: String,
+    },
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    #[error("The request was cancelled")]
+    Cancelled,
+}
+
+impl Error {
+    /// The chain id from a wallet's "There is no chain info for ..." error, which means the chain
+    /// has to be suggested before it can be enabled.
+    pub fn unknown_chain_id(&self) -> Option<&str> {
+        let Error::JavaScript(message) = self else {
+            return None;
+        };
+        let (_, chain_id) = message.split_once("chain info for ")?;
+        Some(chain_id.trim()).filter(|chain_id| !chain_id.is_empty())
+    }
 }
 
 impl From<web_sys::wasm_bindgen::JsValue> for Error {
@@ -25,3 +69,9 @@ impl From<serde_wasm_bindgen::Error> for Error {
         Error::Serialization(message)
     }
 }
+
+impl From<Error> for web_sys::wasm_bindgen::JsValue {
+    fn from(error: Error) -> Self {
+        web_sys::js_sys::Error::new(&error.to_string()).into()
+    }
+}
@@ -0,0 +1,49 @@
+//! Window events emitted by wallet extensions.
+
+use crate::Error;
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast},
+    Event,
+};
+
+/// A listener registered on `window`. The callback is removed when this is dropped.
+#[must_use = "the listener is removed when dropped"]
+pub struct EventListener {
+    event: String,
+    callback: Closure<dyn FnMut(Event)>,
+}
+
+impl EventListener {
+    pub fn new(event: &str, callback: impl FnMut(Event) + 'static) -> Result<Self, Error> {
+        let window = web_sys::window().ok_or(Error::KeplrUnavailable)?;
+        let callback = Closure::<dyn FnMut(Event)>::new(callback);
+        window.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())?;
+        Ok(Self {
+            event: event.to_string(),
+            callback,
+        })
+    }
+
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            let _ = window.remove_event_listener_with_callback(
+                &self.event,
+                self.callback.as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+impl std::fmt::Debug for EventListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventListener")
+            .field("event", &self.event)
+            .finish()
+    }
+}
@@ -0,0 +1,115 @@
+//! Gas simulation and fee estimation, so fees passed to Keplr don't have to be guessed.
+
+use crate::{
+    lcd::Lcd,
+    sign_types::{Coin, StdFee},
+    suggest_chain_types::FeeCurrency,
+    Error,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Which of a fee currency's gas prices to pay.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GasPriceLevel {
+    Low,
+    #[default]
+    Average,
+    High,
+}
+
+impl GasPriceLevel {
+    pub fn gas_price(&self, currency: &FeeCurrency) -> f64 {
+        let step = &currency.gas_price_step;
+        match self {
+            GasPriceLevel::Low => step.low,
+            GasPriceLevel::Average => step.average,
+            GasPriceLevel::High => step.high,
+        }
+    }
+}
+
+/// Gas reported by a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasInfo {
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+}
+
+#[derive(Deserialize)]
+struct SimulateResponse {
+    gas_info: RawGasInfo,
+}
+
+#[derive(Deserialize)]
+struct RawGasInfo {
+    gas_wanted: String,
+    gas_used: String,
+}
+
+/// Estimates fees by simulating transactions against an LCD endpoint.
+#[derive(Clone, Debug)]
+pub struct FeeEstimator {
+    lcd: Lcd,
+    gas_adjustment: f64,
+}
+
+impl FeeEstimator {
+    /// The multiplier applied to simulated gas when none is set, the same as Keplr's.
+    pub const DEFAULT_GAS_ADJUSTMENT: f64 = 1.3;
+
+    pub fn new(lcd: Lcd) -> Self {
+        Self {
+            lcd,
+            gas_adjustment: Self::DEFAULT_GAS_ADJUSTMENT,
+        }
+    }
+
+    /// Sets the multiplier applied to the simulated gas, to leave headroom for state changes
+    /// between simulation and execution.
+    pub fn gas_adjustment(mut self, gas_adjustment: f64) -> Self {
+        self.gas_adjustment = gas_adjustment;
+        self
+    }
+
+    /// Simulates an encoded `Tx`. Signatures are not checked, so they may be empty.
+    pub async fn simulate(&self, tx_bytes: &[u8]) -> Result<GasInfo, Error> {
+        let body = json!({ "tx_bytes": BASE64_STANDARD.encode(tx_bytes) });
+        let response: SimulateResponse =
+            self.lcd.post("/cosmos/tx/v1beta1/simulate", &body).await?;
+        let parse = |gas: &str| {
+            gas.parse()
+                .map_err(|_| Error::Serialization(format!("invalid gas: {gas}")))
+        };
+        Ok(GasInfo {
+            gas_wanted: parse(&response.gas_info.gas_wanted)?,
+            gas_used: parse(&response.gas_info.gas_used)?,
+        })
+    }
+
+    /// Simulates an encoded `Tx` and returns the fee for the adjusted gas.
+    pub async fn estimate(
+        &self,
+        tx_bytes: &[u8],
+        currency: &FeeCurrency,
+        level: GasPriceLevel,
+    ) -> Result<StdFee, Error> {
+        let gas_info = self.simulate(tx_bytes).await?;
+        let gas = (gas_info.gas_used as f64 * self.gas_adjustment).ceil() as u64;
+        Ok(fee_for_gas(gas, currency, level))
+    }
+}
+
+/// Returns the fee for `gas`, paid in `currency` at the given price level.
+pub fn fee_for_gas(gas: u64, currency: &FeeCurrency, level: GasPriceLevel) -> StdFee {
+    let amount = (gas as f64 * level.gas_price(currency)).ceil() as u128;
+    StdFee {
+        amount: vec![Coin {
+            denom: currency.coin_minimal_denom.clone(),
+            amount: amount.to_string(),
+        }],
+        gas: gas.to_string(),
+        ..Default::default()
+    }
+}
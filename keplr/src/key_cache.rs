@@ -0,0 +1,148 @@
+//! Memoized keys, for code that needs the current key often, e.g. on every render.
+
+use crate::{events::EventListener, Error, Keplr, Key};
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+type KeyFuture = Shared<LocalBoxFuture<'static, Result<Key, Error>>>;
+type Fetcher = dyn Fn(String) -> LocalBoxFuture<'static, Result<Key, Error>>;
+
+/// Caches [`Keplr::get_key`] per chain id.
+///
+/// Concurrent requests for the same chain share one call to the wallet. Entries are dropped
+/// when the user switches accounts and when chains are disabled through the handle, failed
+/// requests aren't cached. Clones share the same cache.
+#[derive(Clone)]
+pub struct KeyCache {
+    keplr: Option<Keplr>,
+    fetch: Rc<Fetcher>,
+    generation: Rc<dyn Fn() -> u64>,
+    entries: Rc<RefCell<Entries>>,
+    _listener: Option<Rc<EventListener>>,
+}
+
+#[derive(Default)]
+struct Entries {
+    keys: HashMap<String, KeyFuture>,
+    /// The generation when the keys were requested.
+    generation: u64,
+}
+
+impl KeyCache {
+    /// Creates an empty cache for `keplr`, listening for account switches.
+    pub fn new(keplr: Keplr) -> Result<Self, Error> {
+        let mut cache = Self::with_fetcher({
+            let keplr = keplr.clone();
+            move |chain_id| {
+                let keplr = keplr.clone();
+                async move { keplr.get_key(&chain_id).await }.boxed_local()
+            }
+        })
+        .with_generation({
+            let keplr = keplr.clone();
+            move || keplr.disabled_count()
+        });
+        let entries = Rc::downgrade(&cache.entries);
+        let listener = keplr.on_keystore_change(move || {
+            if let Some(entries) = entries.upgrade() {
+                entries.borrow_mut().keys.clear();
+            }
+        })?;
+        cache.keplr = Some(keplr);
+        cache._listener = Some(Rc::new(listener));
+        Ok(cache)
+    }
+
+    /// Creates an empty cache that gets keys from `fetch`, e.g. from a wallet other than Keplr.
+    /// It isn't cleared on account switches, call [`clear`](Self::clear) for that.
+    pub fn with_fetcher(
+        fetch: impl Fn(String) -> LocalBoxFuture<'static, Result<Key, Error>> + 'static,
+    ) -> Self {
+        Self {
+            keplr: None,
+            fetch: Rc::new(fetch),
+            generation: Rc::new(|| 0),
+            entries: Rc::default(),
+            _listener: None,
+        }
+    }
+
+    /// Drops all keys whenever `generation` returns a new value, like
+    /// [`new`](Self::new) does when chains are disabled.
+    pub fn with_generation(mut self, generation: impl Fn() -> u64 + 'static) -> Self {
+        self.generation = Rc::new(generation);
+        self.entries.borrow_mut().generation = (self.generation)();
+        self
+    }
+
+    /// The handle the keys are requested from, unless the cache was created
+    /// [`with_fetcher`](Self::with_fetcher).
+    pub fn keplr(&self) -> Option<&Keplr> {
+        self.keplr.as_ref()
+    }
+
+    /// Returns the cached key for `chain_id`, or requests it from the wallet.
+    pub async fn get(&self, chain_id: &str) -> Result<Key, Error> {
+        let key = self.entry(chain_id);
+        let result = key.clone().await;
+        if result.is_err() {
+            let mut entries = self.entries.borrow_mut();
+            // unless it was replaced in the meantime
+            if entries
+                .keys
+                .get(chain_id)
+                .is_some_and(|entry| entry.ptr_eq(&key))
+            {
+                entries.keys.remove(chain_id);
+            }
+        }
+        result
+    }
+
+    /// The key for `chain_id` if it was fetched already.
+    pub fn peek(&self, chain_id: &str) -> Option<Key> {
+        self.sync();
+        let entries = self.entries.borrow();
+        entries.keys.get(chain_id)?.peek()?.clone().ok()
+    }
+
+    pub fn invalidate(&self, chain_id: &str) {
+        self.entries.borrow_mut().keys.remove(chain_id);
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().keys.clear();
+    }
+
+    fn entry(&self, chain_id: &str) -> KeyFuture {
+        self.sync();
+        let mut entries = self.entries.borrow_mut();
+        entries
+            .keys
+            .entry(chain_id.to_string())
+            .or_insert_with(|| (self.fetch)(chain_id.to_string()).shared())
+            .clone()
+    }
+
+    /// Drops all keys if the generation changed since they were requested.
+    fn sync(&self) {
+        let generation = (self.generation)();
+        let mut entries = self.entries.borrow_mut();
+        if entries.generation != generation {
+            entries.keys.clear();
+            entries.generation = generation;
+        }
+    }
+}
+
+impl std::fmt::Debug for KeyCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self.entries.borrow();
+        let mut chain_ids: Vec<_> = entries.keys.keys().collect();
+        chain_ids.sort();
+        f.debug_struct("KeyCache")
+            .field("keplr", &self.keplr)
+            .field("chain_ids", &chain_ids)
+            .finish()
+    }
+}
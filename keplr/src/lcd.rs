@@ -0,0 +1,108 @@
+//! A minimal client for a chain's LCD (REST) endpoint, the `rest` URL of a chain info.
+
+use crate::Error;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Clone, Debug)]
+pub struct Lcd {
+    url: String,
+    client: reqwest::Client,
+}
+
+/// The outcome of a request to a resource that may not exist yet, see [`Lcd::poll`].
+pub(crate) enum Polled<T> {
+    Ready(T),
+    /// Nothing exists at the path yet.
+    Pending,
+    /// The node couldn't be reached or failed with a server error, the request may succeed
+    /// when retried.
+    Unavailable(Error),
+}
+
+/// Body of a failed gRPC-gateway request.
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+impl Lcd {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(url, reqwest::Client::new())
+    }
+
+    /// Uses a preconfigured client, e.g. with timeouts or extra headers.
+    pub fn with_client(url: impl Into<String>, client: reqwest::Client) -> Self {
+        let url = url.into().trim_end_matches('/').to_string();
+        Self { url, client }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends a GET request to `path`, e.g. `/cosmos/bank/v1beta1/balances/{address}`, and
+    /// decodes the JSON response.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let request = self.client.get(format!("{}{path}", self.url));
+        Self::send(request).await
+    }
+
+    /// Like [`get`](Self::get), but returns `None` if nothing exists at `path` (yet).
+    pub async fn get_optional<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>, Error> {
+        match self.poll(path).await? {
+            Polled::Ready(value) => Ok(Some(value)),
+            Polled::Pending => Ok(None),
+            Polled::Unavailable(error) => Err(error),
+        }
+    }
+
+    /// Like [`get_optional`](Self::get_optional), but tells errors worth retrying apart from
+    /// ones that aren't.
+    pub(crate) async fn poll<T: DeserializeOwned>(&self, path: &str) -> Result<Polled<T>, Error> {
+        let response = match self.client.get(format!("{}{path}", self.url)).send().await {
+            Ok(response) => response,
+            Err(error) => return Ok(Polled::Unavailable(error.into())),
+        };
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Polled::Pending);
+        }
+        match Self::decode(response).await {
+            Ok(value) => Ok(Polled::Ready(value)),
+            Err(error) if status.is_server_error() => Ok(Polled::Unavailable(error)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Sends `body` as JSON to `path` and decodes the JSON response.
+    pub async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, Error> {
+        let request = self.client.post(format!("{}{path}", self.url)).json(body);
+        Self::send(request).await
+    }
+
+    async fn send<T: DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T, Error> {
+        Self::decode(request.send().await?).await
+    }
+
+    async fn decode<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+        let status = response.status();
+        let body = response.bytes().await?;
+        if !status.is_success() {
+            let message = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|error| error.message)
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            return Err(Error::Request(format!("{status}: {message}")));
+        }
+        serde_json::from_slice(&body).map_err(|e| Error::Serialization(e.to_string()))
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::Request(error.to_string())
+    }
+}
@@ -0,0 +1,203 @@
+//! Reactive wallet state for Leptos apps.
+//!
+//! Wrap the app in a [`KeplrProvider`] and call [`use_keplr`] in any component below it:
+//!
+//! ```ignore
+//! #[component]
+//! fn Connect() -> impl IntoView {
+//!     let keplr = use_keplr();
+//!     let key = keplr.key("secret-4");
+//!     view! {
+//!         <button
+//!             disabled=move || !keplr.is_available().get()
+//!             on:click=move |_| { keplr.connect().dispatch(vec!["secret-4".to_string()]); }
+//!         >
+//!             {move || key.get().map_or("Connect".to_string(), |key| key.bech32_address)}
+//!         </button>
+//!     }
+//! }
+//! ```
+
+use crate::{
+    connection::{self, ConnectionAction, ConnectionState, Connections},
+    events::EventListener,
+    provider::WalletProvider,
+    Error, Keplr, Key,
+};
+use ::leptos::{prelude::*, task::spawn_local};
+use std::collections::BTreeSet;
+
+/// Wallet state shared through the Leptos context. Cheap to copy into closures.
+#[derive(Clone, Copy)]
+pub struct KeplrContext {
+    keplr: StoredValue<Option<Keplr>, LocalStorage>,
+    listeners: StoredValue<Vec<EventListener>, LocalStorage>,
+    available: RwSignal<bool>,
+    connections: RwSignal<Connections>,
+    connect: Action<Vec<String>, Result<(), Error>>,
+    disconnect: Action<String, Result<(), Error>>,
+}
+
+impl KeplrContext {
+    /// Creates the state for `provider`, which has to be called inside a reactive owner, like a
+    /// component. If the wallet isn't injected yet, it's looked up again once the page loaded.
+    pub fn new(provider: WalletProvider) -> Self {
+        let keplr = StoredValue::new_local(None::<Keplr>);
+        let available = RwSignal::new(false);
+        let connections = RwSignal::new(Connections::default());
+
+        let connect = Action::new_unsync(move |chain_ids: &Vec<String>| {
+            let chain_ids = chain_ids.clone();
+            async move {
+                connections.update(|connections| {
+                    for chain_id in &chain_ids {
+                        connections.apply(chain_id, ConnectionAction::Connect);
+                    }
+                });
+                let actions = connection::connect_all(keplr.get_value().as_ref(), &chain_ids).await;
+                let error = actions.iter().find_map(|(_, action)| match action {
+                    ConnectionAction::Failed(error) => Some(error.clone()),
+                    _ => None,
+                });
+                connections.update(|connections| {
+                    for (chain_id, action) in actions {
+                        connections.apply(&chain_id, action);
+                    }
+                });
+                error.map_or(Ok(()), Err)
+            }
+        });
+        let disconnect = Action::new_unsync(move |chain_id: &String| {
+            let chain_id = chain_id.clone();
+            async move {
+                let action = connection::disconnect(keplr.get_value().as_ref(), &chain_id).await;
+                let result = match &action {
+                    ConnectionAction::Failed(error) => Err(error.clone()),
+                    _ => Ok(()),
+                };
+                connections.update(|connections| connections.apply(&chain_id, action));
+                result
+            }
+        });
+
+        let context = Self {
+            keplr,
+            listeners: StoredValue::new_local(Vec::new()),
+            available,
+            connections,
+            connect,
+            disconnect,
+        };
+        if let Some(listener) = connection::attach_on_load(provider, move |keplr| {
+            context.attach(keplr);
+        }) {
+            context
+                .listeners
+                .update_value(|listeners| listeners.push(listener));
+        }
+        context
+    }
+
+    /// Keeps the wallet handle and starts following account switches.
+    fn attach(&self, keplr: Keplr) {
+        let context = *self;
+        if let Ok(listener) = keplr.on_keystore_change(move || {
+            let Some(keplr) = context.keplr.get_value() else {
+                return;
+            };
+            let chain_ids = context
+                .connections
+                .with_untracked(|connections| connections.connected().map(String::from).collect());
+            spawn_local(refresh_keys(keplr, chain_ids, context.connections));
+        }) {
+            self.listeners
+                .update_value(|listeners| listeners.push(listener));
+        }
+        self.keplr.set_value(Some(keplr));
+        self.available.set(true);
+    }
+
+    /// The wallet handle, once it's available. Not reactive, use
+    /// [`is_available`](Self::is_available) to track it.
+    pub fn keplr(&self) -> Option<Keplr> {
+        self.keplr.get_value()
+    }
+
+    pub fn is_available(&self) -> ReadSignal<bool> {
+        self.available.read_only()
+    }
+
+    /// Chains connected through [`connect`](Self::connect).
+    pub fn enabled_chains(&self) -> Signal<BTreeSet<String>> {
+        let connections = self.connections;
+        Signal::derive(move || {
+            connections.with(|connections| connections.connected().map(String::from).collect())
+        })
+    }
+
+    /// The connection to `chain_id`, including the last error.
+    pub fn state(&self, chain_id: impl Into<String>) -> Signal<ConnectionState> {
+        let chain_id = chain_id.into();
+        let connections = self.connections;
+        Signal::derive(move || connections.with(|connections| connections.state(&chain_id)))
+    }
+
+    /// The current key for `chain_id`, updated when the user switches accounts.
+    pub fn key(&self, chain_id: impl Into<String>) -> Signal<Option<Key>> {
+        let chain_id = chain_id.into();
+        let connections = self.connections;
+        Signal::derive(move || connections.with(|connections| connections.key(&chain_id).cloned()))
+    }
+
+    /// Enables the dispatched chains and fetches their keys. Fails with the first error, the
+    /// error of each chain is in its [`state`](Self::state).
+    pub fn connect(&self) -> Action<Vec<String>, Result<(), Error>> {
+        self.connect
+    }
+
+    /// Disables the dispatched chain and forgets its key.
+    pub fn disconnect(&self) -> Action<String, Result<(), Error>> {
+        self.disconnect
+    }
+
+    /// Revokes the permissions for every chain at once.
+    pub fn disconnect_all(&self) {
+        if let Some(keplr) = self.keplr.get_value() {
+            keplr.disable_origin();
+        }
+        self.connections.set(Connections::default());
+    }
+}
+
+/// Fetches the key of each chain again. A chain that fails keeps its previous key and records
+/// the error, without holding up the others.
+async fn refresh_keys(keplr: Keplr, chain_ids: Vec<String>, connections: RwSignal<Connections>) {
+    for chain_id in chain_ids {
+        let action = connection::refresh(&keplr, &chain_id).await;
+        if let ConnectionAction::Failed(error) = &action {
+            tracing::warn!("failed to refresh the key for {chain_id}: {error}");
+        }
+        connections.update(|connections| connections.apply(&chain_id, action));
+    }
+}
+
+/// Provides a [`KeplrContext`] to its children.
+#[component]
+pub fn KeplrProvider(
+    /// The wallet to connect to, Keplr by default.
+    #[prop(optional)]
+    provider: Option<WalletProvider>,
+    children: Children,
+) -> impl IntoView {
+    provide_context(KeplrContext::new(provider.unwrap_or(WalletProvider::Keplr)));
+    children()
+}
+
+/// Returns the [`KeplrContext`] of the enclosing [`KeplrProvider`].
+///
+/// # Panics
+///
+/// Panics when called outside a [`KeplrProvider`].
+pub fn use_keplr() -> KeplrContext {
+    expect_context()
+}
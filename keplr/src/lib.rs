@@ -1,134 +1,693 @@
 mod error;
 pub use error::Error;
 
+#[cfg(feature = "cosmrs")]
+mod cosmrs_compat;
+
+pub mod address;
+#[cfg(feature = "cosmrs")]
+pub mod amino;
+#[cfg(feature = "auth")]
+pub mod auth;
+#[cfg(feature = "broadcast")]
+pub mod broadcast;
+pub mod chain_info;
+pub mod chain_registry;
+#[cfg(any(feature = "yew", feature = "dioxus", feature = "leptos"))]
+pub mod connection;
+#[cfg(feature = "dioxus")]
+pub mod dioxus;
+pub mod encoding;
+pub mod events;
+#[cfg(feature = "fee")]
+pub mod fee;
+pub mod key_cache;
+#[cfg(feature = "lcd")]
+pub mod lcd;
+#[cfg(feature = "leptos")]
+pub mod leptos;
+#[cfg(feature = "tracing-wasm")]
+pub mod logging;
+#[cfg(feature = "presets")]
+pub mod presets;
+#[cfg(feature = "cosmrs")]
+pub mod proto;
+pub mod provider;
+pub mod queue;
+#[cfg(feature = "rpc")]
+pub mod rpc;
+pub mod send;
+pub mod session;
+pub mod sign_types;
+pub mod timeout;
+#[cfg(feature = "cosmrs")]
+pub mod tx;
+pub mod wallet;
+#[cfg(feature = "yew")]
+pub mod yew;
+
 use base64::prelude::{Engine as _, BASE64_STANDARD};
+use events::EventListener;
+use provider::WalletProvider;
+use queue::RequestQueue;
 use serde::{Deserialize, Serialize};
-use std::rc::Rc;
+use session::{Session, SessionStore};
+use sign_types::*;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+    time::Duration,
+};
+use suggest_chain_types::SuggestingChainInfo;
 use web_sys::{
-    console,
     js_sys::{self, JsString},
-    wasm_bindgen,
+    wasm_bindgen::{JsCast, JsValue},
 };
 
-pub struct Keplr {}
+/// A handle to a Keplr provider object, which may belong to any wallet implementing the Keplr
+/// API (see [`WalletProvider`]).
+///
+/// Clones share the same provider and configuration.
+#[derive(Clone)]
+pub struct Keplr {
+    inner: Rc<keplr_sys::Keplr>,
+    provider: Option<WalletProvider>,
+    state: Rc<RefCell<State>>,
+    queue: RequestQueue,
+}
+
+#[derive(Default)]
+struct State {
+    default_options: SignOptions,
+    enabled_chains: BTreeSet<String>,
+    timeout: Option<Duration>,
+    session: Option<SessionStore>,
+    /// Bumped whenever chains are disabled, so caches know their keys may be stale.
+    disabled: u64,
+}
+
+impl From<keplr_sys::Keplr> for Keplr {
+    fn from(value: keplr_sys::Keplr) -> Self {
+        Self {
+            inner: Rc::new(value),
+            provider: None,
+            state: Rc::default(),
+            queue: RequestQueue::default(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Keplr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keplr")
+            .field("provider", &self.provider)
+            .field("enabled_chains", &self.state.borrow().enabled_chains)
+            .field("default_options", &self.state.borrow().default_options)
+            .field("timeout", &self.state.borrow().timeout)
+            .field("session", &self.state.borrow().session)
+            .finish()
+    }
+}
 
 impl Keplr {
-    pub fn debug() {
-        keplr_sys::KEPLR.with(console::log_1)
+    /// Returns a handle to `window.keplr`, or [`Error::KeplrUnavailable`] if the extension isn't
+    /// installed.
+    pub fn new() -> Result<Self, Error> {
+        Self::detect().ok_or(Error::KeplrUnavailable)
+    }
+
+    /// Returns a handle to `window.keplr` if the extension is installed.
+    pub fn detect() -> Option<Self> {
+        Self::with_provider(WalletProvider::Keplr).ok()
+    }
+
+    /// Returns a handle to another wallet's Keplr-compatible provider, such as `window.leap`.
+    pub fn with_provider(provider: WalletProvider) -> Result<Self, Error> {
+        let object = provider
+            .lookup()
+            .ok_or_else(|| Error::WalletUnavailable(provider.name().to_string()))?;
+        Ok(Self {
+            provider: Some(provider),
+            ..Self::from_js_value(object)?
+        })
+    }
+
+    /// Wraps any object implementing the Keplr API, e.g. a wallet that isn't listed in
+    /// [`WalletProvider`], or a test double.
+    pub fn from_js_value(object: JsValue) -> Result<Self, Error> {
+        if !object.is_object() {
+            return Err(Error::KeplrUnavailable);
+        }
+        Ok(object.unchecked_into::<keplr_sys::Keplr>().into())
     }
 
     pub fn is_available() -> bool {
-        web_sys::window()
-            .and_then(|window| {
-                js_sys::Reflect::get(&window, &wasm_bindgen::JsValue::from_str("keplr")).ok()
-            })
-            .map_or(false, |keplr| !keplr.is_undefined() && !keplr.is_null())
+        WalletProvider::Keplr.is_installed()
     }
 
-    pub async fn ping() -> Result<(), Error> {
-        keplr_sys::ping().await.map_err(Into::into)
+    /// The wallet this handle was created for, if it was created from a [`WalletProvider`].
+    pub fn provider(&self) -> Option<WalletProvider> {
+        self.provider
     }
 
-    pub async fn enable(chain_ids: Vec<String>) -> Result<(), Error> {
-        keplr_sys::enable(chain_ids).await.map_err(Into::into)
+    /// Logs the provider and its configuration at debug level.
+    pub fn debug(&self) {
+        tracing::debug!(keplr = ?self);
     }
 
-    pub async fn get_key(chain_id: &str) -> Result<Key, Error> {
-        keplr_sys::get_key(chain_id)
+    /// Version of the injected provider, e.g. `"0.12.100"`.
+    pub fn version(&self) -> Option<String> {
+        self.inner.version().as_string()
+    }
+
+    /// Options used for every sign request made through this handle, unless overridden per
+    /// call.
+    pub fn default_options(&self) -> SignOptions {
+        self.state.borrow().default_options.clone()
+    }
+
+    pub fn set_default_options(&self, options: SignOptions) {
+        self.state.borrow_mut().default_options = options;
+    }
+
+    /// Timeout for every wallet request made through this handle and the signers it creates.
+    /// `None`, the default, waits until the wallet answers.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.state.borrow().timeout
+    }
+
+    /// Sets the [`timeout`](Self::timeout). Requests that run into it fail with
+    /// [`Error::Timeout`], see [`timeout`](mod@timeout) for bounding a single call instead.
+    /// Time spent waiting in the [`queue`] doesn't count.
+    pub fn set_timeout(&self, timeout: Option<Duration>) {
+        self.state.borrow_mut().timeout = timeout;
+    }
+
+    /// The queue interactive requests made through this handle and its signers wait in.
+    pub fn request_queue(&self) -> &RequestQueue {
+        &self.queue
+    }
+
+    /// Chain ids that were enabled through this handle.
+    pub fn enabled_chains(&self) -> Vec<String> {
+        self.state.borrow().enabled_chains.iter().cloned().collect()
+    }
+
+    /// Where the chains enabled through this handle are remembered, see [`session`].
+    pub fn session_store(&self) -> Option<SessionStore> {
+        self.state.borrow().session.clone()
+    }
+
+    pub fn set_session_store(&self, store: Option<SessionStore>) {
+        self.state.borrow_mut().session = store;
+    }
+
+    /// Records chains as enabled without asking the wallet, for sessions that were restored.
+    pub(crate) fn mark_enabled(&self, chain_ids: impl IntoIterator<Item = String>) {
+        self.state.borrow_mut().enabled_chains.extend(chain_ids);
+    }
+
+    /// Updates the saved session, if there is a store. Failing to persist it isn't worth failing
+    /// the request over, e.g. when storage is disabled.
+    fn update_session(&self, update: impl FnOnce(&mut Session)) {
+        let Some(store) = self.session_store() else {
+            return;
+        };
+        if let Err(error) = store.update(update) {
+            tracing::warn!("failed to save the session: {error}");
+        }
+    }
+
+    /// Fetches a key without queueing, giving up after `timeout`, see [`session::restore`].
+    pub(crate) async fn get_key_within(
+        &self,
+        chain_id: &str,
+        timeout: Duration,
+    ) -> Result<Key, Error> {
+        let key = timeout::within(Some(timeout), self.inner.get_key(chain_id)).await?;
+        Ok(serde_wasm_bindgen::from_value(key)?)
+    }
+
+    /// Counts calls to [`disable`](Self::disable) and [`disable_origin`](Self::disable_origin).
+    pub(crate) fn disabled_count(&self) -> u64 {
+        self.state.borrow().disabled
+    }
+
+    /// Runs a request that may open a popup once the ones before it finished, see [`queue`].
+    async fn interactive<T, E: Into<Error>>(
+        &self,
+        request: impl std::future::Future<Output = Result<T, E>>,
+    ) -> Result<T, Error> {
+        self.queue
+            .run(timeout::within(self.timeout(), request))
             .await
-            .and_then(|key| Ok(serde_wasm_bindgen::from_value::<Key>(key)?))
-            .map_err(Into::into)
     }
 
+    fn sign_options(&self, overrides: Option<&SignOptions>) -> Result<JsValue, Error> {
+        let options = match overrides {
+            Some(overrides) => self.state.borrow().default_options.merge(overrides),
+            None => self.default_options(),
+        };
+        Ok(encoding::to_js(&options)?)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, ret(level = "debug"), err(level = "warn"))]
+    pub async fn ping(&self) -> Result<(), Error> {
+        timeout::within(self.timeout(), self.inner.ping()).await
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_ids = ?chain_ids),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn enable(&self, chain_ids: Vec<String>) -> Result<(), Error> {
+        self.interactive(self.inner.enable(chain_ids.clone()))
+            .await?;
+        self.update_session(|session| {
+            for chain_id in &chain_ids {
+                if !session.chain_ids.contains(chain_id) {
+                    session.chain_ids.push(chain_id.clone());
+                }
+            }
+        });
+        self.mark_enabled(chain_ids);
+        Ok(())
+    }
+
+    /// Enables `chains`, suggesting the chains the wallet doesn't know yet with their info.
+    /// Unlike [`enable`](Self::enable), one failing chain doesn't keep the others from being
+    /// enabled.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_ids = ?chains.keys()),
+        ret(level = "debug")
+    )]
+    pub async fn enable_or_suggest(
+        &self,
+        chains: &BTreeMap<String, SuggestingChainInfo>,
+    ) -> EnableReport {
+        let report = enable_or_suggest_with(
+            chains,
+            |chain_ids| self.enable(chain_ids),
+            |chain_info| self.suggest_chain(chain_info),
+        )
+        .await;
+        if !report.failed.is_empty() {
+            tracing::warn!(failed = ?report.failed.keys(), "some chains couldn't be enabled");
+        }
+        report
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_info.chain_id),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn suggest_chain(&self, chain_info: &SuggestingChainInfo) -> Result<(), Error> {
+        let chain_info = encoding::to_js(chain_info)?;
+        self.interactive(self.inner.suggest_chain(chain_info)).await
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id),
+        err(level = "warn")
+    )]
+    pub async fn get_key(&self, chain_id: &str) -> Result<Key, Error> {
+        let enabled = self.state.borrow().enabled_chains.contains(chain_id);
+        // the wallet may ask to unlock or approve the chain first
+        let key = match enabled {
+            true => timeout::within(self.timeout(), self.inner.get_key(chain_id)).await?,
+            false => self.interactive(self.inner.get_key(chain_id)).await?,
+        };
+        let key: Key = serde_wasm_bindgen::from_value(key)?;
+        self.update_session(|session| {
+            // one account per session, the one of the first chain
+            if session.chain_ids.first().map(String::as_str) == Some(chain_id) {
+                session.key_fingerprint = Some(session::fingerprint(&key));
+            }
+        });
+        tracing::debug!(address = %key.bech32_address, "got the key");
+        Ok(key)
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id),
+        err(level = "warn")
+    )]
     pub async fn get_accounts(&self, chain_id: &str) -> Result<Vec<AccountData>, Error> {
-        keplr_sys::get_offline_signer(chain_id)
-            .get_accounts()
-            .await
-            .map_err(Into::into)
-            .map(|val| js_sys::Array::from(&val))
-            .and_then(|accounts| {
-                accounts
-                    .iter()
-                    .map(|account| serde_wasm_bindgen::from_value(account).map_err(Into::into))
-                    .collect::<Result<Vec<AccountData>, Error>>()
-            })
+        self.get_offline_signer(chain_id)?.get_accounts().await
+    }
+
+    pub fn get_offline_signer(&self, chain_id: &str) -> Result<KeplrOfflineSigner, Error> {
+        let options = self.sign_options(None)?;
+        Ok(KeplrOfflineSigner {
+            inner: Rc::new(self.inner.get_offline_signer(chain_id, options)),
+            timeout: self.timeout(),
+            queue: self.queue.clone(),
+        })
+    }
+
+    pub fn get_offline_signer_only_amino(
+        &self,
+        chain_id: &str,
+    ) -> Result<KeplrOfflineSignerOnlyAmino, Error> {
+        let options = self.sign_options(None)?;
+        Ok(KeplrOfflineSignerOnlyAmino {
+            inner: Rc::new(self.inner.get_offline_signer_only_amino(chain_id, options)),
+            timeout: self.timeout(),
+            queue: self.queue.clone(),
+        })
+    }
+
+    /// Like [`Keplr::get_offline_signer`], with options that override the default options for
+    /// every request made through the signer.
+    pub fn get_offline_signer_with_options(
+        &self,
+        chain_id: &str,
+        options: &SignOptions,
+    ) -> Result<KeplrOfflineSigner, Error> {
+        let options = self.sign_options(Some(options))?;
+        Ok(KeplrOfflineSigner {
+            inner: Rc::new(self.inner.get_offline_signer(chain_id, options)),
+            timeout: self.timeout(),
+            queue: self.queue.clone(),
+        })
     }
 
-    pub fn get_offline_signer(chain_id: &str) -> KeplrOfflineSigner {
-        keplr_sys::get_offline_signer(chain_id).into()
+    /// Like [`Keplr::get_offline_signer_only_amino`], with options that override the default
+    /// options for every request made through the signer.
+    pub fn get_offline_signer_only_amino_with_options(
+        &self,
+        chain_id: &str,
+        options: &SignOptions,
+    ) -> Result<KeplrOfflineSignerOnlyAmino, Error> {
+        let options = self.sign_options(Some(options))?;
+        Ok(KeplrOfflineSignerOnlyAmino {
+            inner: Rc::new(self.inner.get_offline_signer_only_amino(chain_id, options)),
+            timeout: self.timeout(),
+            queue: self.queue.clone(),
+        })
+    }
+
+    /// Returns an amino-only signer for Ledger accounts, which can't sign protobuf messages, and
+    /// a direct signer otherwise.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn get_offline_signer_auto(&self, chain_id: &str) -> Result<OfflineSigner, Error> {
+        let key = self.get_key(chain_id).await?;
+        let signer = match key.is_nano_ledger {
+            true => OfflineSigner::Amino(self.get_offline_signer_only_amino(chain_id)?),
+            false => OfflineSigner::Direct(self.get_offline_signer(chain_id)?),
+        };
+        Ok(signer)
+    }
+
+    pub fn get_enigma_utils(&self, chain_id: &str) -> EnigmaUtils {
+        EnigmaUtils {
+            inner: Rc::new(self.inner.get_enigma_utils(chain_id)),
+            timeout: self.timeout(),
+        }
+    }
+
+    /// Requests an amino (`LEGACY_AMINO_JSON`) signature. `options` override the default
+    /// options for this call only.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, signer = %signer),
+        err(level = "warn")
+    )]
+    pub async fn sign_amino(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        sign_doc: &StdSignDoc,
+        options: Option<&SignOptions>,
+    ) -> Result<AminoSignResponse, Error> {
+        let sign_doc = encoding::to_js(sign_doc)?;
+        let options = self.sign_options(options)?;
+        let response = self
+            .interactive(self.inner.sign_amino(chain_id, signer, sign_doc, options))
+            .await?;
+        let response = serde_wasm_bindgen::from_value(response)?;
+        tracing::debug!("signed");
+        Ok(response)
+    }
+
+    /// Requests a protobuf (`DIRECT`) signature. `options` override the default options for
+    /// this call only.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, signer = %signer),
+        err(level = "warn")
+    )]
+    pub async fn sign_direct(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        sign_doc: &SignDoc,
+        options: Option<&SignOptions>,
+    ) -> Result<DirectSignResponse, Error> {
+        let sign_doc = encoding::to_js(sign_doc)?;
+        let options = self.sign_options(options)?;
+        let response = self
+            .interactive(self.inner.sign_direct(chain_id, signer, sign_doc, options))
+            .await?;
+        let response = serde_wasm_bindgen::from_value(response)?;
+        tracing::debug!("signed");
+        Ok(response)
+    }
+
+    /// Signs arbitrary data following ADR-036, e.g. to prove ownership of an address.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, signer = %signer),
+        err(level = "warn")
+    )]
+    pub async fn sign_arbitrary(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+    ) -> Result<StdSignature, Error> {
+        let signature = self
+            .interactive(self.inner.sign_arbitrary(chain_id, signer, data))
+            .await?;
+        let signature: StdSignature = serde_wasm_bindgen::from_value(signature)?;
+        // the signature carries the public key
+        tracing::debug!(len = signature.signature.len(), "signed");
+        Ok(signature)
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, signer = %signer),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn verify_arbitrary(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+        signature: &StdSignature,
+    ) -> Result<bool, Error> {
+        let signature = encoding::to_js(signature)?;
+        let valid = timeout::within(
+            self.timeout(),
+            self.inner
+                .verify_arbitrary(chain_id, signer, data, signature),
+        )
+        .await?;
+        Ok(valid.is_truthy())
     }
 
-    pub fn get_offline_signer_only_amino(chain_id: &str) -> KeplrOfflineSignerOnlyAmino {
-        keplr_sys::get_offline_signer_only_amino(chain_id).into()
+    /// Calls `callback` whenever the user switches accounts in the wallet, until the returned
+    /// listener is dropped.
+    pub fn on_keystore_change(
+        &self,
+        mut callback: impl FnMut() + 'static,
+    ) -> Result<EventListener, Error> {
+        let event = self
+            .provider
+            .unwrap_or(WalletProvider::Keplr)
+            .keystore_change_event();
+        EventListener::new(event, move |_| callback())
     }
 
-    // TODO: does it make sense to have an "auto" method?
-    // pub async fn get_offline_signer_auto(
-    //     chain_id: &str,
-    // ) -> Result<Box<dyn Signer>, Error> {
-    //     let key = Self::get_key(chain_id).await?;
-    //     let signer: Box<dyn Signer> = match key.is_nano_ledger {
-    //         true => Box::new(Self::get_offline_signer_only_amino(chain_id)),
-    //         false => Box::new(Self::get_offline_signer(chain_id)),
-    //     };
-    //     Ok(signer)
-    // }
+    /// Broadcasts a signed `TxRaw` through the wallet's node and returns the tx hash.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, mode = ?mode),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn send_tx(
+        &self,
+        chain_id: &str,
+        tx: &[u8],
+        mode: BroadcastMode,
+    ) -> Result<Vec<u8>, Error> {
+        let hash = timeout::within(
+            self.timeout(),
+            self.inner.send_tx(chain_id, tx, mode.as_str()),
+        )
+        .await?;
+        Ok(js_sys::Uint8Array::new(&hash).to_vec())
+    }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, contract_address = %contract_address),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
     pub async fn suggest_token(
+        &self,
         chain_id: &str,
         contract_address: &str,
         viewing_key: Option<&str>,
     ) -> Result<(), Error> {
-        keplr_sys::suggest_token(chain_id, contract_address, viewing_key)
-            .await
-            .map_err(Into::into)
+        self.interactive(
+            self.inner
+                .suggest_token(chain_id, contract_address, viewing_key),
+        )
+        .await
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id, contract_address = %contract_address),
+        err(level = "warn")
+    )]
     pub async fn get_secret_20_viewing_key(
+        &self,
         chain_id: &str,
         contract_address: &str,
     ) -> Result<String, Error> {
-        keplr_sys::get_secret_20_viewing_key(chain_id, contract_address)
-            .await
-            .map(|foo| JsString::from(foo).into())
-            .map_err(Into::into)
+        let viewing_key = timeout::within(
+            self.timeout(),
+            self.inner
+                .get_secret_20_viewing_key(chain_id, contract_address),
+        )
+        .await?;
+        // the key itself must not end up in the logs
+        tracing::debug!("got the viewing key");
+        Ok(JsString::from(viewing_key).into())
     }
 
-    pub fn disable(chain_id: &str) {
-        keplr_sys::disable(chain_id)
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = %chain_id),
+        ret(level = "debug"),
+        err(level = "warn")
+    )]
+    pub async fn disable(&self, chain_id: &str) -> Result<(), Error> {
+        timeout::within(self.timeout(), self.inner.disable(chain_id)).await?;
+        {
+            let mut state = self.state.borrow_mut();
+            state.enabled_chains.remove(chain_id);
+            state.disabled += 1;
+        }
+        self.update_session(|session| {
+            if session.chain_ids.first().map(String::as_str) == Some(chain_id) {
+                session.key_fingerprint = None;
+            }
+            session.chain_ids.retain(|id| id != chain_id);
+        });
+        Ok(())
     }
 
-    pub fn disable_origin() {
-        keplr_sys::disable_origin()
+    pub fn disable_origin(&self) {
+        self.inner.disable_origin();
+        {
+            let mut state = self.state.borrow_mut();
+            state.enabled_chains.clear();
+            state.disabled += 1;
+        }
+        if let Some(store) = self.session_store() {
+            if let Err(error) = store.clear() {
+                tracing::warn!("failed to clear the session: {error}");
+            }
+        }
     }
 }
 
 #[derive(Clone)]
 pub struct KeplrOfflineSigner {
     inner: Rc<keplr_sys::KeplrOfflineSigner>,
+    timeout: Option<Duration>,
+    queue: RequestQueue,
+}
+
+impl std::fmt::Debug for KeplrOfflineSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeplrOfflineSigner")
+            .field("chain_id", &self.try_chain_id())
+            .finish_non_exhaustive()
+    }
 }
 
 impl From<keplr_sys::KeplrOfflineSigner> for KeplrOfflineSigner {
     fn from(value: keplr_sys::KeplrOfflineSigner) -> Self {
         Self {
             inner: Rc::new(value),
+            timeout: None,
+            queue: RequestQueue::default(),
         }
     }
 }
 
 impl KeplrOfflineSigner {
+    /// # Panics
+    ///
+    /// Panics if the wallet returned an object without a chain id, see
+    /// [`try_chain_id`](Self::try_chain_id).
+    #[deprecated(note = "panics if the wallet left out the chain id, use `try_chain_id`")]
     pub fn chain_id(&self) -> String {
-        self.inner
-            .chain_id()
-            .as_string()
-            .expect("chain_id field is missing!")
+        self.try_chain_id().expect("chain_id field is missing!")
+    }
+
+    pub fn try_chain_id(&self) -> Option<String> {
+        self.inner.chain_id().as_string()
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref()),
+        err(level = "warn")
+    )]
     pub async fn get_accounts(&self) -> Result<Vec<AccountData>, Error> {
-        self.inner
-            .get_accounts()
+        timeout::within(self.timeout, self.inner.get_accounts())
             .await
-            .map_err(|_| Error::KeplrUnavailable)
+            .map_err(|error| match error {
+                Error::Timeout(_) => error,
+                _ => Error::KeplrUnavailable,
+            })
             .map(|val| js_sys::Array::from(&val))
             .and_then(|accounts| {
                 accounts
@@ -136,65 +695,110 @@ impl KeplrOfflineSigner {
                     .map(|account| serde_wasm_bindgen::from_value(account).map_err(Into::into))
                     .collect::<Result<Vec<AccountData>, Error>>()
             })
+            .inspect(|accounts| tracing::debug!(count = accounts.len(), "got the accounts"))
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref(), signer = %signer_address),
+        err(level = "warn")
+    )]
+    pub async fn sign_amino(
+        &self,
+        signer_address: &str,
+        sign_doc: &StdSignDoc,
+    ) -> Result<AminoSignResponse, Error> {
+        let sign_doc = encoding::to_js(sign_doc)?;
+        let response = self
+            .queue
+            .run(timeout::within(
+                self.timeout,
+                self.inner.sign_amino(signer_address.to_string(), sign_doc),
+            ))
+            .await?;
+        let response = serde_wasm_bindgen::from_value(response)?;
+        tracing::debug!("signed");
+        Ok(response)
     }
 
-    // NOTE: anything related to signing should be out of scope for this crate?
-
-    // async fn get_sign_mode(&self) -> Result<SignMode, Error> {
-    //     Ok(SignMode::Direct)
-    // }
-    //
-    // async fn sign_amino(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: StdSignDoc,
-    // ) -> Result<AminoSignResponse, Error> {
-    //     todo!()
-    // }
-    //
-    // async fn sign_permit(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: StdSignDoc,
-    // ) -> Result<AminoSignResponse, Error> {
-    //     todo!()
-    // }
-    //
-    // async fn sign_direct(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: SignDoc,
-    // ) -> Result<DirectSignResponse, Error> {
-    //     todo!()
-    // }
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref(), signer = %signer_address),
+        err(level = "warn")
+    )]
+    pub async fn sign_direct(
+        &self,
+        signer_address: &str,
+        sign_doc: &SignDoc,
+    ) -> Result<DirectSignResponse, Error> {
+        let sign_doc = encoding::to_js(sign_doc)?;
+        let response = self
+            .queue
+            .run(timeout::within(
+                self.timeout,
+                self.inner.sign_direct(signer_address.to_string(), sign_doc),
+            ))
+            .await?;
+        let response = serde_wasm_bindgen::from_value(response)?;
+        tracing::debug!("signed");
+        Ok(response)
+    }
 }
 
 #[derive(Clone)]
 pub struct KeplrOfflineSignerOnlyAmino {
     inner: Rc<keplr_sys::KeplrOfflineSignerOnlyAmino>,
+    timeout: Option<Duration>,
+    queue: RequestQueue,
+}
+
+impl std::fmt::Debug for KeplrOfflineSignerOnlyAmino {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeplrOfflineSignerOnlyAmino")
+            .field("chain_id", &self.try_chain_id())
+            .finish_non_exhaustive()
+    }
 }
 
 impl From<keplr_sys::KeplrOfflineSignerOnlyAmino> for KeplrOfflineSignerOnlyAmino {
     fn from(value: keplr_sys::KeplrOfflineSignerOnlyAmino) -> Self {
         Self {
             inner: Rc::new(value),
+            timeout: None,
+            queue: RequestQueue::default(),
         }
     }
 }
 
 impl KeplrOfflineSignerOnlyAmino {
+    /// # Panics
+    ///
+    /// Panics if the wallet returned an object without a chain id, see
+    /// [`try_chain_id`](Self::try_chain_id).
+    #[deprecated(note = "panics if the wallet left out the chain id, use `try_chain_id`")]
     pub fn chain_id(&self) -> String {
-        self.inner
-            .chain_id()
-            .as_string()
-            .expect("chain_id field is missing!")
+        self.try_chain_id().expect("chain_id field is missing!")
+    }
+
+    pub fn try_chain_id(&self) -> Option<String> {
+        self.inner.chain_id().as_string()
     }
 
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref()),
+        err(level = "warn")
+    )]
     pub async fn get_accounts(&self) -> Result<Vec<AccountData>, Error> {
-        self.inner
-            .get_accounts()
+        timeout::within(self.timeout, self.inner.get_accounts())
             .await
-            .map_err(|_| Error::KeplrUnavailable)
+            .map_err(|error| match error {
+                Error::Timeout(_) => error,
+                _ => Error::KeplrUnavailable,
+            })
             .map(|val| js_sys::Array::from(&val))
             .and_then(|accounts| {
                 accounts
@@ -202,37 +806,270 @@ impl KeplrOfflineSignerOnlyAmino {
                     .map(|account| serde_wasm_bindgen::from_value(account).map_err(Into::into))
                     .collect::<Result<Vec<AccountData>, Error>>()
             })
+            .inspect(|accounts| tracing::debug!(count = accounts.len(), "got the accounts"))
     }
 
-    // NOTE: anything related to signing should be out of scope for this crate?
-
-    // async fn get_sign_mode(&self) -> Result<SignMode, Error> {
-    //     Ok(SignMode::LegacyAminoJson)
-    // }
-    //
-    // async fn sign_amino(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: StdSignDoc,
-    // ) -> Result<AminoSignResponse, Error> {
-    //     todo!()
-    // }
-    //
-    // async fn sign_permit(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: StdSignDoc,
-    // ) -> Result<AminoSignResponse, Error> {
-    //     todo!()
-    // }
-    //
-    // async fn sign_direct(
-    //     &self,
-    //     signer_address: &str,
-    //     sign_doc: SignDoc,
-    // ) -> Result<DirectSignResponse, Error> {
-    //     unimplemented!()
-    // }
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref(), signer = %signer_address),
+        err(level = "warn")
+    )]
+    pub async fn sign_amino(
+        &self,
+        signer_address: &str,
+        sign_doc: &StdSignDoc,
+    ) -> Result<AminoSignResponse, Error> {
+        let sign_doc = encoding::to_js(sign_doc)?;
+        let response = self
+            .queue
+            .run(timeout::within(
+                self.timeout,
+                self.inner.sign_amino(signer_address.to_string(), sign_doc),
+            ))
+            .await?;
+        let response = serde_wasm_bindgen::from_value(response)?;
+        tracing::debug!("signed");
+        Ok(response)
+    }
+}
+
+/// Per-chain outcome of [`Keplr::enable_or_suggest`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnableReport {
+    /// Chains that are enabled now.
+    pub enabled: Vec<String>,
+    /// Chains that were unknown to the wallet and suggested.
+    pub suggested: Vec<String>,
+    /// Chains that couldn't be enabled, with the reason.
+    pub failed: BTreeMap<String, Error>,
+}
+
+impl EnableReport {
+    /// Whether every chain was enabled.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// [`Keplr::enable_or_suggest`] with other `enable` and `suggest` requests, e.g. for a wallet
+/// other than Keplr.
+///
+/// Chains whose key doesn't match the `chain_id` of their info fail without being requested.
+pub async fn enable_or_suggest_with<'a, E, EF, S, SF>(
+    chains: &'a BTreeMap<String, SuggestingChainInfo>,
+    mut enable: E,
+    mut suggest: S,
+) -> EnableReport
+where
+    E: FnMut(Vec<String>) -> EF,
+    EF: std::future::Future<Output = Result<(), Error>>,
+    S: FnMut(&'a SuggestingChainInfo) -> SF,
+    SF: std::future::Future<Output = Result<(), Error>>,
+{
+    let mut report = EnableReport::default();
+    let mut pending = Vec::new();
+    for (chain_id, chain_info) in chains {
+        match chain_info.chain_id == *chain_id {
+            true => pending.push(chain_id.clone()),
+            false => {
+                let error = Error::Request(format!(
+                    "the chain info for {chain_id} is for {}",
+                    chain_info.chain_id
+                ));
+                report.failed.insert(chain_id.clone(), error);
+            }
+        }
+    }
+    while !pending.is_empty() {
+        let error = match enable(pending.clone()).await {
+            Ok(()) => {
+                report.enabled.append(&mut pending);
+                break;
+            }
+            Err(error) => error,
+        };
+        let unknown = error
+            .unknown_chain_id()
+            .and_then(|chain_id| pending.iter().position(|id| id == chain_id));
+        let Some(position) = unknown else {
+            // not something suggesting can fix
+            for chain_id in pending.drain(..) {
+                report.failed.insert(chain_id, error.clone());
+            }
+            break;
+        };
+        let chain_id = pending.remove(position);
+        if report.suggested.contains(&chain_id) {
+            report.failed.insert(chain_id, error);
+            continue;
+        }
+        match suggest(&chains[&chain_id]).await {
+            Ok(()) => {
+                report.suggested.push(chain_id.clone());
+                pending.push(chain_id);
+            }
+            Err(error) => {
+                report.failed.insert(chain_id, error);
+            }
+        }
+    }
+    report
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BroadcastMode {
+    /// Return after the tx passed `CheckTx`.
+    #[default]
+    Sync,
+    /// Return immediately.
+    Async,
+    /// Return after the tx was included in a block. Removed in Cosmos SDK v0.47.
+    Block,
+}
+
+impl BroadcastMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BroadcastMode::Sync => "sync",
+            BroadcastMode::Async => "async",
+            BroadcastMode::Block => "block",
+        }
+    }
+}
+
+/// Either kind of offline signer, see [`Keplr::get_offline_signer_auto`].
+#[derive(Clone, Debug)]
+pub enum OfflineSigner {
+    Direct(KeplrOfflineSigner),
+    Amino(KeplrOfflineSignerOnlyAmino),
+}
+
+impl OfflineSigner {
+    /// # Panics
+    ///
+    /// Panics if the wallet returned an object without a chain id, see
+    /// [`try_chain_id`](Self::try_chain_id).
+    #[deprecated(note = "panics if the wallet left out the chain id, use `try_chain_id`")]
+    pub fn chain_id(&self) -> String {
+        self.try_chain_id().expect("chain_id field is missing!")
+    }
+
+    pub fn try_chain_id(&self) -> Option<String> {
+        match self {
+            OfflineSigner::Direct(signer) => signer.try_chain_id(),
+            OfflineSigner::Amino(signer) => signer.try_chain_id(),
+        }
+    }
+
+    pub async fn get_accounts(&self) -> Result<Vec<AccountData>, Error> {
+        match self {
+            OfflineSigner::Direct(signer) => signer.get_accounts().await,
+            OfflineSigner::Amino(signer) => signer.get_accounts().await,
+        }
+    }
+
+    pub async fn sign_amino(
+        &self,
+        signer_address: &str,
+        sign_doc: &StdSignDoc,
+    ) -> Result<AminoSignResponse, Error> {
+        match self {
+            OfflineSigner::Direct(signer) => signer.sign_amino(signer_address, sign_doc).await,
+            OfflineSigner::Amino(signer) => signer.sign_amino(signer_address, sign_doc).await,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EnigmaUtils {
+    inner: Rc<keplr_sys::EnigmaUtils>,
+    timeout: Option<Duration>,
+}
+
+impl From<keplr_sys::EnigmaUtils> for EnigmaUtils {
+    fn from(value: keplr_sys::EnigmaUtils) -> Self {
+        Self {
+            inner: Rc::new(value),
+            timeout: None,
+        }
+    }
+}
+
+impl EnigmaUtils {
+    /// # Panics
+    ///
+    /// Panics if the wallet returned an object without a chain id, see
+    /// [`try_chain_id`](Self::try_chain_id).
+    #[deprecated(note = "panics if the wallet left out the chain id, use `try_chain_id`")]
+    pub fn chain_id(&self) -> String {
+        self.try_chain_id().expect("chain_id field is missing!")
+    }
+
+    pub fn try_chain_id(&self) -> Option<String> {
+        self.inner.chain_id().as_string()
+    }
+
+    /// Encrypts a contract message. The result is `nonce (32) | pubkey (32) | ciphertext`.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref(), code_hash = %contract_code_hash),
+        err(level = "warn")
+    )]
+    pub async fn encrypt<T: Serialize>(
+        &self,
+        contract_code_hash: &str,
+        msg: &T,
+    ) -> Result<Vec<u8>, Error> {
+        let msg = encoding::to_js(msg)?;
+        timeout::within(
+            self.timeout,
+            self.inner.encrypt(contract_code_hash.to_string(), msg),
+        )
+        .await
+        .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+        .inspect(|ciphertext| tracing::debug!(len = ciphertext.len(), "encrypted"))
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref()),
+        err(level = "warn")
+    )]
+    pub async fn decrypt(&self, ciphertext: &[u8], nonce: &[u8]) -> Result<Vec<u8>, Error> {
+        let plaintext = timeout::within(self.timeout, self.inner.decrypt(ciphertext, nonce))
+            .await
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())?;
+        // the plaintext must not end up in the logs
+        tracing::debug!(len = plaintext.len(), "decrypted");
+        Ok(plaintext)
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref()),
+        err(level = "warn")
+    )]
+    pub async fn get_pubkey(&self) -> Result<Vec<u8>, Error> {
+        timeout::within(self.timeout, self.inner.get_pubkey())
+            .await
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+    }
+
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(chain_id = self.try_chain_id().as_deref()),
+        err(level = "warn")
+    )]
+    pub async fn get_tx_encryption_key(&self, nonce: &[u8]) -> Result<Vec<u8>, Error> {
+        timeout::within(self.timeout, self.inner.get_tx_encryption_key(nonce))
+            .await
+            .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec())
+    }
 }
 
 #[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -241,7 +1078,9 @@ pub struct Key {
     /// Name of the selected key store.
     pub name: String,
     pub algo: String,
+    #[serde(with = "encoding::base64")]
     pub pub_key: Vec<u8>,
+    #[serde(with = "encoding::base64")]
     pub address: Vec<u8>,
     pub bech32_address: String,
     pub ethereum_hex_address: String,
@@ -272,6 +1111,7 @@ impl std::fmt::Debug for Key {
 pub struct AccountData {
     pub address: String,
     pub algo: Algo,
+    #[serde(with = "encoding::base64")]
     pub pubkey: Vec<u8>,
 }
 
@@ -286,17 +1126,39 @@ impl std::fmt::Debug for AccountData {
 }
 
 /// Algorithm types used for signing.
+///
+/// Serialized by variant name as before; the lowercase names Keplr and cosmjs use are accepted
+/// when deserializing.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Algo {
+    #[serde(alias = "secp256k1")]
     Secp256k1,
+    #[serde(rename = "ethsecp256k1")]
+    Ethsecp256k1,
+    #[serde(alias = "ed25519")]
     Ed25519,
+    #[serde(alias = "sr25519")]
     Sr25519,
 }
 
+impl std::str::FromStr for Algo {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "secp256k1" => Ok(Algo::Secp256k1),
+            "ethsecp256k1" => Ok(Algo::Ethsecp256k1),
+            "ed25519" => Ok(Algo::Ed25519),
+            "sr25519" => Ok(Algo::Sr25519),
+            _ => Err(Error::Address(format!("unknown algorithm: {s}"))),
+        }
+    }
+}
+
 pub mod suggest_chain_types {
     use serde::{Deserialize, Serialize};
 
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct SuggestingChainInfo {
         pub chain_id: String,
@@ -310,13 +1172,13 @@ pub mod suggest_chain_types {
         pub stake_currency: Currency,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct Bip44 {
         pub coin_type: u32,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct Bech32Config {
         pub bech32_prefix_acc_addr: String,
@@ -327,26 +1189,82 @@ pub mod suggest_chain_types {
         pub bech32_prefix_cons_pub: String,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    impl Bech32Config {
+        /// The usual prefixes derived from the account prefix, e.g. `secretvaloper` for `secret`.
+        pub fn from_prefix(prefix: &str) -> Self {
+            Self {
+                bech32_prefix_acc_addr: prefix.to_string(),
+                bech32_prefix_acc_pub: format!("{prefix}pub"),
+                bech32_prefix_val_addr: format!("{prefix}valoper"),
+                bech32_prefix_val_pub: format!("{prefix}valoperpub"),
+                bech32_prefix_cons_addr: format!("{prefix}valcons"),
+                bech32_prefix_cons_pub: format!("{prefix}valconspub"),
+            }
+        }
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct Currency {
         pub coin_denom: String,
         pub coin_minimal_denom: String,
         pub coin_decimals: u8,
+        /// Left out when empty, the wallet rejects empty ids.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
         pub coin_gecko_id: String,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    impl Currency {
+        /// A currency without a CoinGecko id.
+        pub fn new(
+            coin_denom: impl Into<String>,
+            coin_minimal_denom: impl Into<String>,
+            coin_decimals: u8,
+        ) -> Self {
+            Self {
+                coin_denom: coin_denom.into(),
+                coin_minimal_denom: coin_minimal_denom.into(),
+                coin_decimals,
+                coin_gecko_id: String::new(),
+            }
+        }
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct FeeCurrency {
         pub coin_denom: String,
         pub coin_minimal_denom: String,
         pub coin_decimals: u8,
+        /// Left out when empty, the wallet rejects empty ids.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
         pub coin_gecko_id: String,
         pub gas_price_step: GasPriceStep,
     }
 
-    #[derive(Serialize, Deserialize, Debug)]
+    impl FeeCurrency {
+        pub fn new(currency: Currency, gas_price_step: GasPriceStep) -> Self {
+            Self {
+                coin_denom: currency.coin_denom,
+                coin_minimal_denom: currency.coin_minimal_denom,
+                coin_decimals: currency.coin_decimals,
+                coin_gecko_id: currency.coin_gecko_id,
+                gas_price_step,
+            }
+        }
+
+        /// The currency without its gas prices.
+        pub fn currency(&self) -> Currency {
+            Currency {
+                coin_denom: self.coin_denom.clone(),
+                coin_minimal_denom: self.coin_minimal_denom.clone(),
+                coin_decimals: self.coin_decimals,
+                coin_gecko_id: self.coin_gecko_id.clone(),
+            }
+        }
+    }
+
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
     #[serde(rename_all = "camelCase")]
     pub struct GasPriceStep {
         pub low: f64,
@@ -0,0 +1,21 @@
+//! Browser console output for the spans and events emitted by this crate.
+//!
+//! Every wallet call is wrapped in a `debug` span named after the method, with the chain id and
+//! other identifiers as fields. Keys, signatures, sign docs and messages are never recorded.
+//! Failed calls emit a `warn` event with the error, successful ones a `debug` event with the
+//! result, or only a summary where the result holds one of those or a secret.
+
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+use tracing_wasm::{WASMLayer, WASMLayerConfigBuilder};
+
+/// Installs a global subscriber that logs to the console and records span durations as
+/// `performance` measures, visible in the browser's performance timeline.
+///
+/// Returns `false` if a global subscriber was already set.
+pub fn init(max_level: tracing::Level) -> bool {
+    let config = WASMLayerConfigBuilder::new()
+        .set_max_level(max_level)
+        .build();
+    tracing::subscriber::set_global_default(Registry::default().with(WASMLayer::new(config)))
+        .is_ok()
+}
@@ -0,0 +1,142 @@
+//! Chain info for common networks, to suggest chains the wallet may not know without
+//! copy-pasting config:
+//!
+//! ```ignore
+//! keplr.suggest_chain(&keplr::presets::LOCALSECRET).await?;
+//! ```
+//!
+//! The endpoints are public ones, apps in production should use their own.
+
+use crate::suggest_chain_types::{
+    Bech32Config, Bip44, Currency, FeeCurrency, GasPriceStep, SuggestingChainInfo,
+};
+use std::sync::LazyLock;
+
+/// Secret Network mainnet, `secret-4`.
+pub static SECRET: LazyLock<SuggestingChainInfo> = LazyLock::new(|| {
+    secret(
+        "secret-4",
+        "Secret Network",
+        "https://rpc.mainnet.secretsaturn.net",
+        "https://lcd.mainnet.secretsaturn.net",
+        "secret",
+    )
+});
+
+/// Secret Network testnet, `pulsar-3`.
+pub static PULSAR: LazyLock<SuggestingChainInfo> = LazyLock::new(|| {
+    secret(
+        "pulsar-3",
+        "Secret Network Testnet",
+        "https://rpc.pulsar3.scrttestnet.com",
+        "https://api.pulsar3.scrttestnet.com",
+        "",
+    )
+});
+
+/// A LocalSecret development node on the default ports, `secretdev-1`. Keplr never knows this
+/// chain, so it always has to be suggested.
+pub static LOCALSECRET: LazyLock<SuggestingChainInfo> = LazyLock::new(|| {
+    secret(
+        "secretdev-1",
+        "LocalSecret",
+        "http://localhost:26657",
+        "http://localhost:1317",
+        "",
+    )
+});
+
+/// Cosmos Hub, `cosmoshub-4`.
+pub static COSMOS_HUB: LazyLock<SuggestingChainInfo> = LazyLock::new(|| {
+    let atom = Currency {
+        coin_gecko_id: "cosmos".to_string(),
+        ..Currency::new("ATOM", "uatom", 6)
+    };
+    SuggestingChainInfo {
+        chain_id: "cosmoshub-4".to_string(),
+        chain_name: "Cosmos Hub".to_string(),
+        rpc: "https://cosmos-rpc.publicnode.com".to_string(),
+        rest: "https://cosmos-rest.publicnode.com".to_string(),
+        bip44: Bip44 { coin_type: 118 },
+        bech32_config: Bech32Config::from_prefix("cosmos"),
+        currencies: vec![atom.clone()],
+        fee_currencies: vec![FeeCurrency::new(
+            atom.clone(),
+            GasPriceStep {
+                low: 0.005,
+                average: 0.025,
+                high: 0.03,
+            },
+        )],
+        stake_currency: atom,
+    }
+});
+
+/// Osmosis, `osmosis-1`.
+pub static OSMOSIS: LazyLock<SuggestingChainInfo> = LazyLock::new(|| {
+    let osmo = Currency {
+        coin_gecko_id: "osmosis".to_string(),
+        ..Currency::new("OSMO", "uosmo", 6)
+    };
+    SuggestingChainInfo {
+        chain_id: "osmosis-1".to_string(),
+        chain_name: "Osmosis".to_string(),
+        rpc: "https://rpc.osmosis.zone".to_string(),
+        rest: "https://lcd.osmosis.zone".to_string(),
+        bip44: Bip44 { coin_type: 118 },
+        bech32_config: Bech32Config::from_prefix("osmo"),
+        currencies: vec![osmo.clone()],
+        fee_currencies: vec![FeeCurrency::new(
+            osmo.clone(),
+            GasPriceStep {
+                low: 0.0025,
+                average: 0.025,
+                high: 0.04,
+            },
+        )],
+        stake_currency: osmo,
+    }
+});
+
+/// All presets.
+pub fn all() -> [&'static SuggestingChainInfo; 5] {
+    [&SECRET, &PULSAR, &LOCALSECRET, &COSMOS_HUB, &OSMOSIS]
+}
+
+/// The preset for `chain_id`, if there is one.
+pub fn find(chain_id: &str) -> Option<&'static SuggestingChainInfo> {
+    all()
+        .into_iter()
+        .find(|chain_info| chain_info.chain_id == chain_id)
+}
+
+fn secret(
+    chain_id: &str,
+    chain_name: &str,
+    rpc: &str,
+    rest: &str,
+    coin_gecko_id: &str,
+) -> SuggestingChainInfo {
+    let scrt = Currency {
+        coin_gecko_id: coin_gecko_id.to_string(),
+        ..Currency::new("SCRT", "uscrt", 6)
+    };
+    SuggestingChainInfo {
+        chain_id: chain_id.to_string(),
+        chain_name: chain_name.to_string(),
+        rpc: rpc.to_string(),
+        rest: rest.to_string(),
+        bip44: Bip44 { coin_type: 529 },
+        bech32_config: Bech32Config::from_prefix("secret"),
+        currencies: vec![scrt.clone()],
+        fee_currencies: vec![FeeCurrency::new(
+            scrt.clone(),
+            GasPriceStep {
+                low: 0.1,
+                average: 0.25,
+                high: 0.5,
+            },
+        )],
+        stake_currency: scrt,
+    }
+}
@@ -0,0 +1,104 @@
+//! Protobuf messages that aren't part of [`cosmrs::proto`].
+
+use cosmrs::proto::cosmos::base::v1beta1::Coin;
+
+/// `ibc.applications.transfer.v1`
+pub mod ibc_transfer {
+    use super::*;
+
+    /// Transfers tokens to another chain over an IBC channel.
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgTransfer {
+        #[prost(string, tag = "1")]
+        pub source_port: String,
+        #[prost(string, tag = "2")]
+        pub source_channel: String,
+        #[prost(message, optional, tag = "3")]
+        pub token: Option<Coin>,
+        #[prost(string, tag = "4")]
+        pub sender: String,
+        #[prost(string, tag = "5")]
+        pub receiver: String,
+        /// Height on the destination chain after which the transfer times out.
+        #[prost(message, optional, tag = "6")]
+        pub timeout_height: Option<Height>,
+        /// Timeout in nanoseconds since the unix epoch, `0` to disable.
+        #[prost(uint64, tag = "7")]
+        pub timeout_timestamp: u64,
+        #[prost(string, tag = "8")]
+        pub memo: String,
+    }
+
+    impl prost::Name for MsgTransfer {
+        const NAME: &'static str = "MsgTransfer";
+        const PACKAGE: &'static str = "ibc.applications.transfer.v1";
+    }
+
+    /// `ibc.core.client.v1.Height`
+    #[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+    pub struct Height {
+        #[prost(uint64, tag = "1")]
+        pub revision_number: u64,
+        #[prost(uint64, tag = "2")]
+        pub revision_height: u64,
+    }
+
+    impl prost::Name for Height {
+        const NAME: &'static str = "Height";
+        const PACKAGE: &'static str = "ibc.core.client.v1";
+    }
+}
+
+/// `secret.compute.v1beta1`
+///
+/// Addresses are raw account bytes rather than bech32 strings, and contract messages must be
+/// encrypted with [`EnigmaUtils::encrypt`](crate::EnigmaUtils::encrypt) first.
+pub mod secret_compute {
+    use super::*;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgInstantiateContract {
+        #[prost(bytes = "vec", tag = "1")]
+        pub sender: Vec<u8>,
+        #[prost(string, tag = "2")]
+        pub callback_code_hash: String,
+        #[prost(uint64, tag = "3")]
+        pub code_id: u64,
+        #[prost(string, tag = "4")]
+        pub label: String,
+        #[prost(bytes = "vec", tag = "5")]
+        pub init_msg: Vec<u8>,
+        #[prost(message, repeated, tag = "6")]
+        pub init_funds: Vec<Coin>,
+        #[prost(bytes = "vec", tag = "7")]
+        pub callback_sig: Vec<u8>,
+        #[prost(string, tag = "8")]
+        pub admin: String,
+    }
+
+    impl prost::Name for MsgInstantiateContract {
+        const NAME: &'static str = "MsgInstantiateContract";
+        const PACKAGE: &'static str = "secret.compute.v1beta1";
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct MsgExecuteContract {
+        #[prost(bytes = "vec", tag = "1")]
+        pub sender: Vec<u8>,
+        #[prost(bytes = "vec", tag = "2")]
+        pub contract: Vec<u8>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub msg: Vec<u8>,
+        #[prost(string, tag = "4")]
+        pub callback_code_hash: String,
+        #[prost(message, repeated, tag = "5")]
+        pub sent_funds: Vec<Coin>,
+        #[prost(bytes = "vec", tag = "6")]
+        pub callback_sig: Vec<u8>,
+    }
+
+    impl prost::Name for MsgExecuteContract {
+        const NAME: &'static str = "MsgExecuteContract";
+        const PACKAGE: &'static str = "secret.compute.v1beta1";
+    }
+}
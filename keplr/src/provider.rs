@@ -0,0 +1,76 @@
+//! Wallets that inject a Keplr-compatible provider object into the page.
+
+use web_sys::{js_sys, wasm_bindgen::JsValue};
+
+/// A wallet extension exposing the Keplr API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WalletProvider {
+    /// `window.keplr`
+    Keplr,
+    /// `window.leap`
+    Leap,
+    /// `window.cosmostation.providers.keplr`
+    Cosmostation,
+}
+
+impl WalletProvider {
+    pub const ALL: [WalletProvider; 3] = [
+        WalletProvider::Keplr,
+        WalletProvider::Leap,
+        WalletProvider::Cosmostation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WalletProvider::Keplr => "Keplr",
+            WalletProvider::Leap => "Leap",
+            WalletProvider::Cosmostation => "Cosmostation",
+        }
+    }
+
+    /// Path to the provider object, starting from `window`.
+    pub fn path(&self) -> &'static [&'static str] {
+        match self {
+            WalletProvider::Keplr => &["keplr"],
+            WalletProvider::Leap => &["leap"],
+            WalletProvider::Cosmostation => &["cosmostation", "providers", "keplr"],
+        }
+    }
+
+    /// Window event fired when the user switches accounts.
+    pub fn keystore_change_event(&self) -> &'static str {
+        match self {
+            WalletProvider::Keplr => "keplr_keystorechange",
+            WalletProvider::Leap => "leap_keystorechange",
+            WalletProvider::Cosmostation => "cosmostation_keystorechange",
+        }
+    }
+
+    pub fn is_installed(&self) -> bool {
+        self.lookup().is_some()
+    }
+
+    /// Returns the injected provider object, if the wallet is installed.
+    pub fn lookup(&self) -> Option<JsValue> {
+        let window: JsValue = web_sys::window()?.into();
+        self.path().iter().try_fold(window, |object, key| {
+            js_sys::Reflect::get(&object, &JsValue::from_str(key))
+                .ok()
+                .filter(|value| !value.is_undefined() && !value.is_null())
+        })
+    }
+}
+
+impl std::fmt::Display for WalletProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the wallets installed in this browser, in [`WalletProvider::ALL`] order.
+pub fn detect_wallets() -> Vec<WalletProvider> {
+    WalletProvider::ALL
+        .into_iter()
+        .filter(WalletProvider::is_installed)
+        .collect()
+}
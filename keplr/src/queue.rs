@@ -0,0 +1,112 @@
+//! Serializes interactive wallet requests.
+//!
+//! Keplr stacks or rejects popups when several requests that need the user arrive at once, so
+//! [`Keplr`](crate::Keplr) runs `enable`, `suggest_*` and `sign_*` one after another, in the
+//! order they were first polled. Requests that don't open a popup, like `get_key` for a chain
+//! that's enabled already, don't wait. For any other chain it may ask to unlock the wallet or
+//! approve the chain, so it's queued too.
+//!
+//! A failed or dropped request simply hands over to the next one, each caller gets its own
+//! result.
+
+use futures::future::poll_fn;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    future::Future,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+/// A first-in, first-out queue of requests. Clones share the same queue.
+#[derive(Clone, Default)]
+pub struct RequestQueue {
+    inner: Rc<RefCell<Queue>>,
+}
+
+#[derive(Default)]
+struct Queue {
+    next_id: u64,
+    /// Requests in order, the first one is running.
+    waiting: VecDeque<(u64, Option<Waker>)>,
+}
+
+impl std::fmt::Debug for RequestQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl RequestQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of requests running or waiting.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().waiting.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Waits until all earlier requests finished, then runs `request`.
+    ///
+    /// Don't await requests on the same queue inside `request`, they would wait for it forever.
+    pub async fn run<F: Future>(&self, request: F) -> F::Output {
+        let ticket = self.enqueue();
+        poll_fn(|cx| ticket.poll_turn(cx)).await;
+        request.await
+    }
+
+    fn enqueue(&self) -> Ticket {
+        let mut queue = self.inner.borrow_mut();
+        let id = queue.next_id;
+        queue.next_id += 1;
+        queue.waiting.push_back((id, None));
+        Ticket {
+            queue: self.inner.clone(),
+            id,
+        }
+    }
+}
+
+/// A place in the queue, given up when dropped.
+struct Ticket {
+    queue: Rc<RefCell<Queue>>,
+    id: u64,
+}
+
+impl Ticket {
+    fn poll_turn(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut queue = self.queue.borrow_mut();
+        match queue.waiting.iter_mut().position(|(id, _)| *id == self.id) {
+            Some(0) => Poll::Ready(()),
+            Some(position) => {
+                queue.waiting[position].1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+            // tickets stay queued until dropped, but never hang the request if that changes
+            None => Poll::Ready(()),
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let next = {
+            let mut queue = self.queue.borrow_mut();
+            queue.waiting.retain(|(id, _)| *id != self.id);
+            queue
+                .waiting
+                .front_mut()
+                .and_then(|(_, waker)| waker.take())
+        };
+        if let Some(waker) = next {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! A minimal client for a chain's Tendermint (CometBFT) RPC endpoint, the `rpc` URL of a chain
+//! info.
+
+use crate::{lcd::Polled, Error};
+use serde::{de::DeserializeOwned, Deserialize};
+
+#[derive(Clone, Debug)]
+pub struct Rpc {
+    url: String,
+    client: reqwest::Client,
+}
+
+/// A JSON-RPC response, which carries either a result or an error.
+#[derive(Deserialize)]
+struct Response<T> {
+    result: Option<T>,
+    error: Option<ErrorResponse>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+    /// The actual reason, e.g. "tx (0A1B...) not found".
+    #[serde(default)]
+    data: String,
+}
+
+impl ErrorResponse {
+    fn into_error(self) -> Error {
+        match self.data.is_empty() {
+            true => Error::Request(self.message),
+            false => Error::Request(format!("{}: {}", self.message, self.data)),
+        }
+    }
+}
+
+impl Rpc {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self::with_client(url, reqwest::Client::new())
+    }
+
+    /// Uses a preconfigured client, e.g. with timeouts or extra headers.
+    pub fn with_client(url: impl Into<String>, client: reqwest::Client) -> Self {
+        let url = url.into().trim_end_matches('/').to_string();
+        Self { url, client }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends a GET request to `path`, e.g. `/status` or `/block?height=5`, and decodes the
+    /// `result` of the JSON-RPC response.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let response = self
+            .client
+            .get(format!("{}{path}", self.url))
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+        match serde_json::from_slice::<Response<T>>(&body) {
+            Ok(Response {
+                result: Some(result),
+                ..
+            }) => Ok(result),
+            Ok(Response {
+                error: Some(error), ..
+            }) => Err(error.into_error()),
+            _ => Err(Self::unexpected(status, &body)),
+        }
+    }
+
+    /// Like [`get`](Self::get), but tells a missing resource and errors worth retrying apart
+    /// from ones that aren't, see [`Lcd::poll`](crate::lcd::Lcd::poll).
+    pub(crate) async fn poll<T: DeserializeOwned>(&self, path: &str) -> Result<Polled<T>, Error> {
+        let response = match self.client.get(format!("{}{path}", self.url)).send().await {
+            Ok(response) => response,
+            Err(error) => return Ok(Polled::Unavailable(error.into())),
+        };
+        let status = response.status();
+        let body = response.bytes().await?;
+        // CometBFT answers every JSON-RPC error with a 500, so only bodies that aren't one
+        // (e.g. from a proxy) are worth retrying
+        match serde_json::from_slice::<Response<T>>(&body) {
+            Ok(Response {
+                result: Some(result),
+                ..
+            }) => Ok(Polled::Ready(result)),
+            Ok(Response {
+                error: Some(error), ..
+            }) if error.data.contains("not found") => Ok(Polled::Pending),
+            Ok(Response {
+                error: Some(error), ..
+            }) => Err(error.into_error()),
+            _ if status.is_server_error() => {
+                Ok(Polled::Unavailable(Self::unexpected(status, &body)))
+            }
+            _ => Err(Self::unexpected(status, &body)),
+        }
+    }
+
+    fn unexpected(status: reqwest::StatusCode, body: &[u8]) -> Error {
+        Error::Request(format!("{status}: {}", String::from_utf8_lossy(body)))
+    }
+}
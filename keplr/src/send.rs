@@ -0,0 +1,137 @@
+//! `Send` versions of the wallet futures, for frameworks and traits that require `Send`.
+//!
+//! Keplr futures hold JS values and aren't `Send`. In the browser wasm runs on a single thread,
+//! so they never actually move between threads; [`SendWrapper`] asserts that, and panics if the
+//! value is used from another thread after all.
+
+use crate::{
+    sign_types::{
+        AminoSignResponse, DirectSignResponse, SignDoc, SignOptions, StdSignDoc, StdSignature,
+    },
+    suggest_chain_types::SuggestingChainInfo,
+    AccountData, BroadcastMode, EnableReport, Error, Keplr, Key, OfflineSigner,
+};
+use send_wrapper::SendWrapper;
+use std::{collections::BTreeMap, future::Future, ops::Deref};
+
+/// Makes any future `Send`, e.g. one returned by a signer:
+///
+/// ```ignore
+/// let response = keplr::send::send(signer.sign_direct(&address, &sign_doc)).await?;
+/// ```
+pub fn send<F: Future>(future: F) -> SendWrapper<F> {
+    SendWrapper::new(future)
+}
+
+/// A [`Keplr`] handle whose async methods return `Send` futures. Other methods are available
+/// through `Deref`.
+#[derive(Clone)]
+pub struct SendKeplr(SendWrapper<Keplr>);
+
+macro_rules! send_methods {
+    ($($(#[$meta:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $output:ty;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name<'a>(&'a self $(, $arg: $ty)*) -> impl Future<Output = $output> + Send + 'a {
+                send(self.0.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl SendKeplr {
+    pub fn new(keplr: Keplr) -> Self {
+        Self(SendWrapper::new(keplr))
+    }
+
+    /// # Panics
+    ///
+    /// Panics when called from a different thread than the one the handle was created on.
+    pub fn into_inner(self) -> Keplr {
+        self.0.take()
+    }
+
+    send_methods! {
+        fn ping(&self) -> Result<(), Error>;
+        fn enable(&self, chain_ids: Vec<String>) -> Result<(), Error>;
+        fn enable_or_suggest(
+            &self,
+            chains: &'a BTreeMap<String, SuggestingChainInfo>
+        ) -> EnableReport;
+        fn suggest_chain(&self, chain_info: &'a SuggestingChainInfo) -> Result<(), Error>;
+        fn get_key(&self, chain_id: &'a str) -> Result<Key, Error>;
+        fn get_accounts(&self, chain_id: &'a str) -> Result<Vec<AccountData>, Error>;
+        fn sign_amino(
+            &self,
+            chain_id: &'a str,
+            signer: &'a str,
+            sign_doc: &'a StdSignDoc,
+            options: Option<&'a SignOptions>
+        ) -> Result<AminoSignResponse, Error>;
+        fn sign_direct(
+            &self,
+            chain_id: &'a str,
+            signer: &'a str,
+            sign_doc: &'a SignDoc,
+            options: Option<&'a SignOptions>
+        ) -> Result<DirectSignResponse, Error>;
+        fn sign_arbitrary(
+            &self,
+            chain_id: &'a str,
+            signer: &'a str,
+            data: &'a [u8]
+        ) -> Result<StdSignature, Error>;
+        fn verify_arbitrary(
+            &self,
+            chain_id: &'a str,
+            signer: &'a str,
+            data: &'a [u8],
+            signature: &'a StdSignature
+        ) -> Result<bool, Error>;
+        fn send_tx(
+            &self,
+            chain_id: &'a str,
+            tx: &'a [u8],
+            mode: BroadcastMode
+        ) -> Result<Vec<u8>, Error>;
+        fn suggest_token(
+            &self,
+            chain_id: &'a str,
+            contract_address: &'a str,
+            viewing_key: Option<&'a str>
+        ) -> Result<(), Error>;
+        fn get_secret_20_viewing_key(
+            &self,
+            chain_id: &'a str,
+            contract_address: &'a str
+        ) -> Result<String, Error>;
+        fn disable(&self, chain_id: &'a str) -> Result<(), Error>;
+    }
+
+    /// The signer isn't `Send` either, so it's wrapped as well.
+    pub fn get_offline_signer_auto<'a>(
+        &'a self,
+        chain_id: &'a str,
+    ) -> impl Future<Output = Result<SendWrapper<OfflineSigner>, Error>> + Send + 'a {
+        send(async move {
+            self.0
+                .get_offline_signer_auto(chain_id)
+                .await
+                .map(SendWrapper::new)
+        })
+    }
+}
+
+impl Deref for SendKeplr {
+    type Target = Keplr;
+
+    fn deref(&self) -> &Keplr {
+        &self.0
+    }
+}
+
+impl From<Keplr> for SendKeplr {
+    fn from(keplr: Keplr) -> Self {
+        Self::new(keplr)
+    }
+}
@@ -0,0 +1,282 @@
+//! Remembering a connection across page reloads.
+//!
+//! Give a handle a [`SessionStore`] and it records the chains enabled through it in
+//! `localStorage`. On the next page load, [`restore`] reconnects the chains the wallet still
+//! authorizes without waiting for the user:
+//!
+//! ```ignore
+//! let keplr = Keplr::new()?;
+//! keplr.set_session_store(Some(SessionStore::default()));
+//! if let Some(restored) = keplr::session::restore(&keplr).await? {
+//!     // connected to `restored.chain_ids()` again
+//! }
+//! ```
+
+use crate::{Error, Keplr, Key};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    cell::RefCell, collections::HashMap, fmt::Write as _, future::Future, rc::Rc, time::Duration,
+};
+
+/// Storage key used by [`SessionStore::default`].
+pub const DEFAULT_STORAGE_KEY: &str = "keplr-session";
+
+/// What is remembered about a connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    pub chain_ids: Vec<String>,
+    /// [`fingerprint`] of the key for the first chain, to notice account switches.
+    pub key_fingerprint: Option<String>,
+}
+
+/// Identifies a key without storing it: the first 8 bytes of the SHA-256 of its public key, as
+/// hex.
+pub fn fingerprint(key: &Key) -> String {
+    Sha256::digest(&key.pub_key)[..8]
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Where a [`SessionStore`] keeps the JSON of a session.
+pub trait SessionStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, Error>;
+
+    fn set(&self, key: &str, value: &str) -> Result<(), Error>;
+
+    fn remove(&self, key: &str) -> Result<(), Error>;
+}
+
+/// The browser's `localStorage`, used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+impl SessionStorage for LocalStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, Error> {
+        Ok(local_storage()?.get_item(key)?)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), Error> {
+        Ok(local_storage()?.set_item(key, value)?)
+    }
+
+    fn remove(&self, key: &str) -> Result<(), Error> {
+        Ok(local_storage()?.remove_item(key)?)
+    }
+}
+
+/// Storage that lives as long as the page, e.g. for tests or to not persist sessions at all.
+/// Clones share the same entries.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage {
+    entries: Rc<RefCell<HashMap<String, String>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStorage for MemoryStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, Error> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<(), Error> {
+        self.entries.borrow_mut().remove(key);
+        Ok(())
+    }
+}
+
+/// A [`Session`] saved under a key, in `localStorage` unless another [`SessionStorage`] is
+/// given.
+#[derive(Clone)]
+pub struct SessionStore {
+    key: String,
+    storage: Rc<dyn SessionStorage>,
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_STORAGE_KEY)
+    }
+}
+
+impl std::fmt::Debug for SessionStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionStore")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionStore {
+    /// A store under `key`, e.g. to keep sessions of several apps on one origin apart.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self::with_storage(key, LocalStorage)
+    }
+
+    pub fn with_storage(key: impl Into<String>, storage: impl SessionStorage + 'static) -> Self {
+        Self {
+            key: key.into(),
+            storage: Rc::new(storage),
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn load(&self) -> Result<Option<Session>, Error> {
+        let Some(json) = self.storage.get(&self.key)? else {
+            return Ok(None);
+        };
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| Error::Serialization(e.to_string()))
+    }
+
+    pub fn save(&self, session: &Session) -> Result<(), Error> {
+        let json =
+            serde_json::to_string(session).map_err(|e| Error::Serialization(e.to_string()))?;
+        self.storage.set(&self.key, &json)
+    }
+
+    pub fn clear(&self) -> Result<(), Error> {
+        self.storage.remove(&self.key)
+    }
+
+    /// Changes the saved session, or starts one if there is none yet. A session without chains
+    /// is removed, and nothing is written if the session didn't change.
+    pub fn update(&self, update: impl FnOnce(&mut Session)) -> Result<(), Error> {
+        let saved = self.load()?;
+        let mut session = saved.clone().unwrap_or_default();
+        update(&mut session);
+        match (session.chain_ids.is_empty(), saved) {
+            (true, None) => Ok(()),
+            (true, Some(_)) => self.clear(),
+            (false, Some(saved)) if saved == session => Ok(()),
+            (false, _) => self.save(&session),
+        }
+    }
+}
+
+fn local_storage() -> Result<web_sys::Storage, Error> {
+    web_sys::window()
+        .ok_or(Error::KeplrUnavailable)?
+        .local_storage()?
+        .ok_or_else(|| Error::JavaScript("localStorage is unavailable".to_string()))
+}
+
+/// A session reconnected by [`restore`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoredSession {
+    pub session: Session,
+    /// Whether the wallet returned a different account than when the session was saved, e.g.
+    /// because the user switched accounts while the page was closed.
+    pub account_changed: bool,
+    /// Chains of the session that weren't reconnected because the wallet didn't answer in time,
+    /// e.g. since it's locked. They stay in the session, and can be connected again by the user.
+    pub skipped: Vec<String>,
+}
+
+impl RestoredSession {
+    /// The chains of the session that were reconnected.
+    pub fn chain_ids(&self) -> impl Iterator<Item = &String> {
+        self.session
+            .chain_ids
+            .iter()
+            .filter(|chain_id| !self.skipped.contains(chain_id))
+    }
+}
+
+/// How long [`restore`] waits for a key before skipping the chain.
+pub const RESTORE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Reconnects the chains saved by the handle's [`SessionStore`] and returns the session, or
+/// `None` if there was nothing to restore.
+///
+/// Keys are requested with `getKey`, outside of the [`queue`](crate::queue) and within
+/// [`RESTORE_TIMEOUT`] (or the handle's [`timeout`](Keplr::timeout), if shorter). An
+/// authorized, unlocked wallet answers right away. If it has to ask the user first, because
+/// it's locked or the origin lost its permission, Keplr opens its popup anyway: the chain is
+/// then skipped instead of waiting for it, see [`RestoredSession::skipped`]. If the wallet
+/// rejects a chain, e.g. because the user revoked the permission in the meantime, the session
+/// is cleared. Other errors are returned and the session is kept for the next attempt.
+pub async fn restore(keplr: &Keplr) -> Result<Option<RestoredSession>, Error> {
+    let Some(store) = keplr.session_store() else {
+        return Ok(None);
+    };
+    let timeout = keplr
+        .timeout()
+        .map_or(RESTORE_TIMEOUT, |timeout| timeout.min(RESTORE_TIMEOUT));
+    let restored = restore_with(&store, |chain_id| async move {
+        keplr.get_key_within(&chain_id, timeout).await
+    })
+    .await?;
+    if let Some(restored) = &restored {
+        keplr.mark_enabled(restored.chain_ids().cloned());
+    }
+    Ok(restored)
+}
+
+/// [`restore`] with the keys from `get_key`, e.g. for a wallet other than Keplr. Chains it
+/// fails with [`Error::Timeout`] for are skipped.
+pub async fn restore_with<F, Fut>(
+    store: &SessionStore,
+    mut get_key: F,
+) -> Result<Option<RestoredSession>, Error>
+where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<Key, Error>>,
+{
+    let Some(mut session) = store.load()? else {
+        return Ok(None);
+    };
+    let (mut first_key, mut skipped) = (None, Vec::new());
+    for (index, chain_id) in session.chain_ids.iter().enumerate() {
+        match get_key(chain_id.clone()).await {
+            Ok(key) if index == 0 => first_key = Some(key),
+            Ok(_) => {}
+            // the wallet is waiting for the user
+            Err(error @ Error::Timeout(_)) => {
+                tracing::debug!("not restoring {chain_id}: {error}");
+                skipped.push(chain_id.clone());
+            }
+            // the wallet answered, but not with a key
+            Err(error @ Error::JavaScript(_)) => {
+                tracing::debug!("not restoring the session, {chain_id} failed: {error}");
+                store.clear()?;
+                return Ok(None);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+    // without the first chain's key, there's nothing to compare the fingerprint with
+    let account_changed = match first_key.as_ref().map(fingerprint) {
+        Some(key_fingerprint) if session.key_fingerprint.as_ref() != Some(&key_fingerprint) => {
+            let changed = session.key_fingerprint.is_some();
+            session.key_fingerprint = Some(key_fingerprint);
+            store.save(&session)?;
+            changed
+        }
+        _ => false,
+    };
+    Ok(Some(RestoredSession {
+        session,
+        account_changed,
+        skipped,
+    }))
+}
@@ -0,0 +1,223 @@
+//! Sign docs, responses and options passed to Keplr's signing methods.
+
+use crate::{encoding, Error};
+use ::base64::prelude::{Engine as _, BASE64_STANDARD};
+use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// Options that change how the Keplr popup treats a sign request.
+///
+/// Unset fields fall back to the options set with
+/// [`Keplr::set_default_options`](crate::Keplr::set_default_options).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignOptions {
+    /// Keep the fee in the sign doc instead of letting the user pick one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer_no_set_fee: Option<bool>,
+    /// Keep the memo in the sign doc instead of letting the user edit it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer_no_set_memo: Option<bool>,
+    /// Don't block signing when the fee exceeds the account balance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_balance_check: Option<bool>,
+}
+
+impl SignOptions {
+    /// Returns these options with every field that is set in `overrides` replaced.
+    pub fn merge(&self, overrides: &SignOptions) -> SignOptions {
+        SignOptions {
+            prefer_no_set_fee: overrides.prefer_no_set_fee.or(self.prefer_no_set_fee),
+            prefer_no_set_memo: overrides.prefer_no_set_memo.or(self.prefer_no_set_memo),
+            disable_balance_check: overrides
+                .disable_balance_check
+                .or(self.disable_balance_check),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StdFee {
+    pub amount: Vec<Coin>,
+    pub gas: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub granter: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub value: serde_json::Value,
+}
+
+/// The document signed in `LEGACY_AMINO_JSON` mode.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StdSignDoc {
+    pub chain_id: String,
+    pub account_number: String,
+    pub sequence: String,
+    pub fee: StdFee,
+    pub msgs: Vec<AminoMsg>,
+    pub memo: String,
+}
+
+impl StdSignDoc {
+    /// The sign doc Keplr builds for [`Keplr::sign_arbitrary`](crate::Keplr::sign_arbitrary), as
+    /// specified by ADR-036.
+    pub fn arbitrary(signer: &str, data: &[u8]) -> Self {
+        Self {
+            chain_id: String::new(),
+            account_number: "0".to_string(),
+            sequence: "0".to_string(),
+            fee: StdFee {
+                gas: "0".to_string(),
+                ..Default::default()
+            },
+            msgs: vec![AminoMsg {
+                type_: "sign/MsgSignData".to_string(),
+                value: serde_json::json!({
+                    "signer": signer,
+                    "data": BASE64_STANDARD.encode(data),
+                }),
+            }],
+            memo: String::new(),
+        }
+    }
+
+    /// The bytes that are signed, identical to Keplr's `serializeSignDoc`: sorted JSON with `&`,
+    /// `<` and `>` escaped.
+    pub fn sign_bytes(&self) -> Result<Vec<u8>, Error> {
+        let json = encoding::to_sorted_json(self)?;
+        Ok(json
+            .replace('&', "\\u0026")
+            .replace('<', "\\u003c")
+            .replace('>', "\\u003e")
+            .into_bytes())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PubKey {
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Base64 encoded public key.
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StdSignature {
+    pub pub_key: PubKey,
+    /// Base64 encoded signature.
+    pub signature: String,
+}
+
+impl StdSignature {
+    /// Checks that this is a valid signature of `sign_doc`, e.g. one returned by
+    /// [`Keplr::sign_amino`](crate::Keplr::sign_amino). Only secp256k1 keys are supported.
+    pub fn verify(&self, sign_doc: &StdSignDoc) -> Result<bool, Error> {
+        self.verify_bytes(&sign_doc.sign_bytes()?)
+    }
+
+    /// Checks that this is a valid signature of `message`.
+    pub fn verify_bytes(&self, message: &[u8]) -> Result<bool, Error> {
+        if self.pub_key.type_ != "tendermint/PubKeySecp256k1" {
+            return Err(Error::Signature(format!(
+                "unsupported public key type: {}",
+                self.pub_key.type_
+            )));
+        }
+        let pubkey = BASE64_STANDARD
+            .decode(&self.pub_key.value)
+            .map_err(|e| Error::Signature(format!("invalid public key: {e}")))?;
+        let key = VerifyingKey::from_sec1_bytes(&pubkey)
+            .map_err(|e| Error::Signature(format!("invalid public key: {e}")))?;
+        let signature = BASE64_STANDARD
+            .decode(&self.signature)
+            .map_err(|e| Error::Signature(format!("invalid signature: {e}")))?;
+        let signature = Signature::from_slice(&signature)
+            .map_err(|e| Error::Signature(format!("invalid signature: {e}")))?;
+        Ok(key.verify(message, &signature).is_ok())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AminoSignResponse {
+    /// The sign doc that was signed. It may differ from the one requested, e.g. when the user
+    /// changed the fee in the popup.
+    pub signed: StdSignDoc,
+    pub signature: StdSignature,
+}
+
+/// The document signed in `DIRECT` mode.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignDoc {
+    #[serde(with = "encoding::uint8array")]
+    pub body_bytes: Vec<u8>,
+    #[serde(with = "encoding::uint8array")]
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: String,
+    #[serde(deserialize_with = "deserialize_long")]
+    pub account_number: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DirectSignResponse {
+    /// The sign doc that was signed. It may differ from the one requested, e.g. when the user
+    /// changed the fee in the popup.
+    pub signed: SignDoc,
+    pub signature: StdSignature,
+}
+
+/// Keplr returns `accountNumber` as a `Long` object; accept that as well as strings and numbers.
+fn deserialize_long<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    struct LongVisitor;
+
+    impl<'de> de::Visitor<'de> for LongVisitor {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string, a number or a Long")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(v.to_string())
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let (mut low, mut high) = (0i64, 0i64);
+            while let Some(key) = map.next_key::<String>()? {
+                match key.as_str() {
+                    "low" => low = map.next_value()?,
+                    "high" => high = map.next_value()?,
+                    _ => {
+                        map.next_value::<de::IgnoredAny>()?;
+                    }
+                }
+            }
+            // `low` and `high` are the signed 32 bit halves of the number
+            let value = ((high as u64) << 32) | (low as u32 as u64);
+            Ok(value.to_string())
+        }
+    }
+
+    deserializer.deserialize_any(LongVisitor)
+}
@@ -0,0 +1,61 @@
+//! Timeouts and cancellation for wallet requests.
+//!
+//! Some wallet versions never settle a request when the user closes the popup instead of
+//! rejecting it. [`Keplr::set_timeout`](crate::Keplr::set_timeout) bounds every request made
+//! through a handle, [`timeout`] bounds a single call.
+//!
+//! Dropping a request's future stops waiting for the wallet, the popup itself stays open.
+//! [`abortable`] does the same from elsewhere, e.g. a "Cancel" button:
+//!
+//! ```ignore
+//! let (sign, handle) = keplr::timeout::abortable(keplr.sign_amino(chain_id, signer, &doc, None));
+//! on_cancel(move || handle.abort());
+//! let response = sign.await?; // Err(Error::Cancelled) once aborted
+//! ```
+
+use crate::Error;
+use futures::{future::Either, pin_mut};
+use futures_timer::Delay;
+use std::{future::Future, time::Duration};
+
+pub use futures::future::AbortHandle;
+
+/// Fails with [`Error::Timeout`] if `future` doesn't complete within `duration`.
+pub async fn timeout<T, E: Into<Error>>(
+    duration: Duration,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, Error> {
+    let deadline = Delay::new(duration);
+    pin_mut!(future);
+    match futures::future::select(future, deadline).await {
+        Either::Left((result, _)) => result.map_err(Into::into),
+        Either::Right(_) => Err(Error::Timeout(format!(
+            "no response from the wallet after {duration:?}"
+        ))),
+    }
+}
+
+/// Returns `future` together with a handle that makes it fail with [`Error::Cancelled`].
+pub fn abortable<T, E: Into<Error>>(
+    future: impl Future<Output = Result<T, E>>,
+) -> (impl Future<Output = Result<T, Error>>, AbortHandle) {
+    let (future, handle) = futures::future::abortable(future);
+    let future = async move {
+        match future.await {
+            Ok(result) => result.map_err(Into::into),
+            Err(_) => Err(Error::Cancelled),
+        }
+    };
+    (future, handle)
+}
+
+/// Applies [`timeout`] if there is one.
+pub(crate) async fn within<T, E: Into<Error>>(
+    duration: Option<Duration>,
+    future: impl Future<Output = Result<T, E>>,
+) -> Result<T, Error> {
+    match duration {
+        Some(duration) => timeout(duration, future).await,
+        None => future.await.map_err(Into::into),
+    }
+}
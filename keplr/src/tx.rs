@@ -0,0 +1,199 @@
+//! Building and signing transactions through Keplr.
+
+use crate::{
+    amino::AminoRegistry,
+    sign_types::{AminoSignResponse, StdFee, StdSignDoc},
+    AccountData, Error, KeplrOfflineSigner, KeplrOfflineSignerOnlyAmino, OfflineSigner,
+};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use cosmrs::{
+    proto::cosmos::tx::v1beta1::TxRaw,
+    tx::{Body, Fee, ModeInfo, Raw, SignMode, SignerInfo},
+    Any,
+};
+
+/// Assembles a transaction from messages, fee and memo, has Keplr sign it and returns the
+/// `TxRaw` bytes, ready for [`Keplr::send_tx`](crate::Keplr::send_tx).
+///
+/// Direct signers sign the protobuf body as is. Amino-only signers (e.g. Ledger accounts) sign
+/// the amino JSON form of the messages, converted with the [`AminoRegistry`].
+#[derive(Debug, Default)]
+pub struct TxBuilder {
+    messages: Vec<Any>,
+    memo: String,
+    fee: StdFee,
+    account_number: u64,
+    sequence: u64,
+    amino: AminoRegistry,
+}
+
+impl TxBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn message(mut self, message: impl Into<Any>) -> Self {
+        self.messages.push(message.into());
+        self
+    }
+
+    pub fn messages(mut self, messages: impl IntoIterator<Item = Any>) -> Self {
+        self.messages.extend(messages);
+        self
+    }
+
+    pub fn memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = memo.into();
+        self
+    }
+
+    pub fn fee(mut self, fee: StdFee) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    pub fn account_number(mut self, account_number: u64) -> Self {
+        self.account_number = account_number;
+        self
+    }
+
+    pub fn sequence(mut self, sequence: u64) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Sets the account number and sequence from an account fetched with
+    /// [`get_account`](crate::auth::get_account).
+    #[cfg(feature = "auth")]
+    pub fn account(self, account: &crate::auth::Account) -> Self {
+        self.account_number(account.account_number)
+            .sequence(account.sequence)
+    }
+
+    /// Replaces the converters used for amino signing, e.g. with [`AminoRegistry::secret`] on
+    /// Secret Network or to add custom messages.
+    pub fn amino_registry(mut self, registry: AminoRegistry) -> Self {
+        self.amino = registry;
+        self
+    }
+
+    /// Encodes the unsigned transaction for `account`, e.g. for
+    /// [`FeeEstimator::simulate`](crate::fee::FeeEstimator::simulate).
+    pub fn simulation_bytes(&self, account: &AccountData) -> Result<Vec<u8>, Error> {
+        let body = Body::new(self.messages.clone(), &self.memo, 0u32);
+        // the fee usually isn't known yet when simulating
+        let fee = match self.fee.gas.is_empty() {
+            true => Fee {
+                amount: Vec::new(),
+                gas_limit: 0,
+                payer: None,
+                granter: None,
+            },
+            false => Fee::try_from(&self.fee)?,
+        };
+        let auth_info =
+            SignerInfo::single_direct(Some(account.public_key()?), self.sequence).auth_info(fee);
+        let raw: Raw = TxRaw {
+            body_bytes: body.into_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            // simulation doesn't check signatures, but expects one per signer
+            signatures: vec![Vec::new()],
+        }
+        .into();
+        Ok(raw.to_bytes()?)
+    }
+
+    /// The amino sign doc for `chain_id`, what [`sign`](Self::sign) has amino-only signers sign.
+    pub fn amino_sign_doc(&self, chain_id: &str) -> Result<StdSignDoc, Error> {
+        Ok(StdSignDoc {
+            chain_id: chain_id.to_string(),
+            account_number: self.account_number.to_string(),
+            sequence: self.sequence.to_string(),
+            fee: self.fee.clone(),
+            msgs: self
+                .messages
+                .iter()
+                .map(|msg| self.amino.to_amino(msg))
+                .collect::<Result<_, _>>()?,
+            memo: self.memo.clone(),
+        })
+    }
+
+    /// Encodes the `TxRaw` for an amino sign `response` of `account`.
+    ///
+    /// The tx is rebuilt from what was actually signed, the user may have changed fee or memo.
+    pub fn amino_tx(
+        &self,
+        account: &AccountData,
+        response: AminoSignResponse,
+    ) -> Result<Vec<u8>, Error> {
+        let signed = response.signed;
+        let messages = signed
+            .msgs
+            .iter()
+            .map(|msg| self.amino.to_proto(msg))
+            .collect::<Result<Vec<_>, _>>()?;
+        let body = Body::new(messages, signed.memo, 0u32);
+        let signer_info = SignerInfo {
+            public_key: Some(account.public_key()?.into()),
+            mode_info: ModeInfo::single(SignMode::LegacyAminoJson),
+            sequence: signed
+                .sequence
+                .parse()
+                .map_err(|_| Error::Tx("invalid sequence".to_string()))?,
+        };
+        let auth_info = signer_info.auth_info(Fee::try_from(&signed.fee)?);
+        let signature = BASE64_STANDARD
+            .decode(response.signature.signature)
+            .map_err(|e| Error::Tx(e.to_string()))?;
+
+        let raw: Raw = TxRaw {
+            body_bytes: body.into_bytes()?,
+            auth_info_bytes: auth_info.into_bytes()?,
+            signatures: vec![signature],
+        }
+        .into();
+        Ok(raw.to_bytes()?)
+    }
+
+    /// Signs with the signer's first account and returns the encoded `TxRaw`.
+    pub async fn sign(&self, signer: &OfflineSigner) -> Result<Vec<u8>, Error> {
+        let account = signer
+            .get_accounts()
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Tx("signer has no accounts".to_string()))?;
+        match signer {
+            OfflineSigner::Direct(signer) => self.sign_direct(signer, &account).await,
+            OfflineSigner::Amino(signer) => self.sign_amino(signer, &account).await,
+        }
+    }
+
+    async fn sign_direct(
+        &self,
+        signer: &KeplrOfflineSigner,
+        account: &AccountData,
+    ) -> Result<Vec<u8>, Error> {
+        let body = Body::new(self.messages.clone(), &self.memo, 0u32);
+        let auth_info = SignerInfo::single_direct(Some(account.public_key()?), self.sequence)
+            .auth_info(Fee::try_from(&self.fee)?);
+        let raw = signer
+            .sign_tx(&account.address, &body, &auth_info, self.account_number)
+            .await?;
+        Ok(raw.to_bytes()?)
+    }
+
+    async fn sign_amino(
+        &self,
+        signer: &KeplrOfflineSignerOnlyAmino,
+        account: &AccountData,
+    ) -> Result<Vec<u8>, Error> {
+        let chain_id = signer
+            .try_chain_id()
+            .ok_or_else(|| Error::Tx("the signer has no chain id".to_string()))?;
+        let sign_doc = self.amino_sign_doc(&chain_id)?;
+        let response = signer.sign_amino(&account.address, &sign_doc).await?;
+        self.amino_tx(account, response)
+    }
+}
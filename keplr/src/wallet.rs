@@ -0,0 +1,135 @@
+//! A wallet-agnostic interface, so app code doesn't have to call [`Keplr`] directly.
+
+use crate::{
+    events::EventListener, provider::WalletProvider, sign_types::StdSignature,
+    suggest_chain_types::SuggestingChainInfo, Error, Keplr, Key, OfflineSigner,
+};
+use async_trait::async_trait;
+
+#[async_trait(?Send)]
+pub trait Wallet {
+    /// Display name of the wallet.
+    fn name(&self) -> &str;
+
+    async fn enable(&self, chain_ids: Vec<String>) -> Result<(), Error>;
+
+    async fn disable(&self, chain_id: &str) -> Result<(), Error>;
+
+    async fn get_key(&self, chain_id: &str) -> Result<Key, Error>;
+
+    /// Returns a signer suited to the current account, see [`Keplr::get_offline_signer_auto`].
+    async fn get_offline_signer(&self, chain_id: &str) -> Result<OfflineSigner, Error>;
+
+    async fn suggest_chain(&self, chain_info: &SuggestingChainInfo) -> Result<(), Error>;
+
+    async fn sign_arbitrary(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+    ) -> Result<StdSignature, Error>;
+
+    /// Calls `callback` whenever the user switches accounts, until the listener is dropped.
+    fn on_keystore_change(&self, callback: Box<dyn FnMut()>) -> Result<EventListener, Error>;
+}
+
+#[async_trait(?Send)]
+impl Wallet for Keplr {
+    fn name(&self) -> &str {
+        self.provider().map_or("Keplr", |provider| provider.name())
+    }
+
+    async fn enable(&self, chain_ids: Vec<String>) -> Result<(), Error> {
+        Keplr::enable(self, chain_ids).await
+    }
+
+    async fn disable(&self, chain_id: &str) -> Result<(), Error> {
+        Keplr::disable(self, chain_id).await
+    }
+
+    async fn get_key(&self, chain_id: &str) -> Result<Key, Error> {
+        Keplr::get_key(self, chain_id).await
+    }
+
+    async fn get_offline_signer(&self, chain_id: &str) -> Result<OfflineSigner, Error> {
+        Keplr::get_offline_signer_auto(self, chain_id).await
+    }
+
+    async fn suggest_chain(&self, chain_info: &SuggestingChainInfo) -> Result<(), Error> {
+        Keplr::suggest_chain(self, chain_info).await
+    }
+
+    async fn sign_arbitrary(
+        &self,
+        chain_id: &str,
+        signer: &str,
+        data: &[u8],
+    ) -> Result<StdSignature, Error> {
+        Keplr::sign_arbitrary(self, chain_id, signer, data).await
+    }
+
+    fn on_keystore_change(&self, callback: Box<dyn FnMut()>) -> Result<EventListener, Error> {
+        Keplr::on_keystore_change(self, callback)
+    }
+}
+
+/// The wallets available to the app.
+#[derive(Default)]
+pub struct WalletRegistry {
+    wallets: Vec<Box<dyn Wallet>>,
+}
+
+impl WalletRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a registry holding every installed [`WalletProvider`].
+    pub fn detect() -> Self {
+        Self::detect_with(|provider| Keplr::with_provider(provider).ok())
+    }
+
+    /// Like [`detect`](Self::detect), with `connect` returning the wallet for a provider, or
+    /// `None` if it isn't installed. Providers are tried in [`WalletProvider::ALL`] order.
+    pub fn detect_with<W: Wallet + 'static>(
+        mut connect: impl FnMut(WalletProvider) -> Option<W>,
+    ) -> Self {
+        let mut registry = Self::new();
+        for wallet in WalletProvider::ALL.into_iter().filter_map(&mut connect) {
+            registry.register(wallet);
+        }
+        registry
+    }
+
+    /// Adds a wallet, e.g. a backend that doesn't implement the Keplr API.
+    pub fn register(&mut self, wallet: impl Wallet + 'static) {
+        self.wallets.push(Box::new(wallet));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Wallet> {
+        self.wallets
+            .iter()
+            .find(|wallet| wallet.name() == name)
+            .map(AsRef::as_ref)
+    }
+
+    pub fn wallets(&self) -> &[Box<dyn Wallet>] {
+        &self.wallets
+    }
+
+    pub fn into_wallets(self) -> Vec<Box<dyn Wallet>> {
+        self.wallets
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wallets.is_empty()
+    }
+}
+
+impl std::fmt::Debug for WalletRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.wallets.iter().map(|wallet| wallet.name()))
+            .finish()
+    }
+}
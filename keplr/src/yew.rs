@@ -0,0 +1,122 @@
+//! Wallet state hooks for Yew apps.
+//!
+//! ```ignore
+//! #[function_component]
+//! fn Connect() -> Html {
+//!     let keplr = use_keplr("secret-4");
+//!     match &keplr.state.key {
+//!         Some(key) => html! { <span>{ &key.bech32_address }</span> },
+//!         None => html! { <button onclick={keplr.connect.reform(|_| ())}>{ "Connect" }</button> },
+//!     }
+//! }
+//! ```
+
+use crate::{
+    connection::{self, ConnectionAction, ConnectionState},
+    provider::WalletProvider,
+    Keplr,
+};
+use ::yew::{platform::spawn_local, prelude::*};
+use std::rc::Rc;
+
+impl Reducible for ConnectionState {
+    type Action = ConnectionAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        Rc::new(self.apply(action))
+    }
+}
+
+/// Returned by [`use_keplr`].
+#[derive(Clone)]
+pub struct UseKeplrHandle {
+    /// `None` if Keplr isn't installed, or not injected yet.
+    pub keplr: Rc<Option<Keplr>>,
+    pub state: UseReducerHandle<ConnectionState>,
+    /// Enables the chain and fetches its key.
+    pub connect: Callback<()>,
+    /// Disables the chain.
+    pub disconnect: Callback<()>,
+}
+
+/// Tracks the connection to `chain_id`. The key is refreshed when the user switches accounts.
+///
+/// If Keplr isn't injected on the first render, it's looked up again once the page loaded.
+#[hook]
+pub fn use_keplr(chain_id: &str) -> UseKeplrHandle {
+    let detected = use_state(|| Rc::new(Keplr::detect()));
+    {
+        let detected = detected.clone();
+        use_effect_with((), move |()| {
+            // the extension may inject itself after the first render
+            let listener = match detected.is_none() {
+                true => connection::attach_on_load(WalletProvider::Keplr, move |keplr| {
+                    detected.set(Rc::new(Some(keplr)));
+                }),
+                false => None,
+            };
+            move || drop(listener)
+        });
+    }
+    let keplr = (*detected).clone();
+    let state = use_reducer(ConnectionState::default);
+
+    let connect = {
+        let (keplr, dispatcher, chain_id) =
+            (keplr.clone(), state.dispatcher(), chain_id.to_string());
+        Callback::from(move |()| {
+            dispatcher.dispatch(ConnectionAction::Connect);
+            let (keplr, dispatcher, chain_id) =
+                (keplr.clone(), dispatcher.clone(), chain_id.clone());
+            spawn_local(async move {
+                dispatcher.dispatch(connection::connect(keplr.as_ref().as_ref(), &chain_id).await);
+            });
+        })
+    };
+    let disconnect = {
+        let (keplr, dispatcher, chain_id) =
+            (keplr.clone(), state.dispatcher(), chain_id.to_string());
+        Callback::from(move |()| {
+            let (keplr, dispatcher, chain_id) =
+                (keplr.clone(), dispatcher.clone(), chain_id.clone());
+            spawn_local(async move {
+                dispatcher
+                    .dispatch(connection::disconnect(keplr.as_ref().as_ref(), &chain_id).await);
+            });
+        })
+    };
+
+    {
+        let (keplr, dispatcher) = (keplr.clone(), state.dispatcher());
+        use_effect_with(
+            (chain_id.to_string(), state.is_connected()),
+            move |(chain_id, connected)| {
+                let chain_id = chain_id.clone();
+                let listener = match (keplr.as_ref(), connected) {
+                    (Some(keplr), true) => {
+                        let handle = keplr.clone();
+                        keplr
+                            .on_keystore_change(move || {
+                                let (keplr, dispatcher, chain_id) =
+                                    (handle.clone(), dispatcher.clone(), chain_id.clone());
+                                spawn_local(async move {
+                                    dispatcher
+                                        .dispatch(connection::refresh(&keplr, &chain_id).await);
+                                });
+                            })
+                            .ok()
+                    }
+                    _ => None,
+                };
+                move || drop(listener)
+            },
+        );
+    }
+
+    UseKeplrHandle {
+        keplr,
+        state,
+        connect,
+        disconnect,
+    }
+}
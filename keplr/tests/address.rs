@@ -0,0 +1,96 @@
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use keplr::{address, AccountData, Algo, Key};
+
+const PUBKEY: &str = "AtQaCqFnshaZQp6rIkvAPyzThvCvXSDO+9AzbxVErqJP";
+const COSMOS_ADDRESS: &str = "cosmos1h806c7khnvmjlywdrkdgk2vrayy2mmvf9rxk2r";
+const SECRET_ADDRESS: &str = "secret1h806c7khnvmjlywdrkdgk2vrayy2mmvf8xjlhl";
+
+fn key() -> Key {
+    let pub_key = BASE64_STANDARD.decode(PUBKEY).unwrap();
+    Key {
+        name: "test".to_string(),
+        algo: "secp256k1".to_string(),
+        address: address::pubkey_to_address(Algo::Secp256k1, &pub_key).unwrap(),
+        pub_key,
+        bech32_address: COSMOS_ADDRESS.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn derives_secp256k1_address() {
+    let pubkey = BASE64_STANDARD.decode(PUBKEY).unwrap();
+    assert_eq!(
+        address::pubkey_to_bech32(Algo::Secp256k1, &pubkey, "cosmos").unwrap(),
+        COSMOS_ADDRESS
+    );
+}
+
+#[test]
+fn derives_ethsecp256k1_address() {
+    // public key of private key 1, i.e. the generator point
+    let pubkey = hex("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    assert_eq!(
+        address::pubkey_to_address(Algo::Ethsecp256k1, &pubkey).unwrap(),
+        hex("7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+    );
+}
+
+#[test]
+fn derives_ed25519_address() {
+    let pubkey = hex("12ee6f581fe55673a1e9e1382a0829e32075a0aa4763c968bc526e1852e78c95");
+    assert_eq!(
+        address::pubkey_to_bech32(Algo::Ed25519, &pubkey, "cosmos").unwrap(),
+        "cosmos1pfq05em6sfkls66ut4m2257p7qwlk448h8mysz"
+    );
+}
+
+#[test]
+fn converts_prefix() {
+    assert_eq!(
+        address::convert_prefix(COSMOS_ADDRESS, "secret").unwrap(),
+        SECRET_ADDRESS
+    );
+    let (prefix, data) = address::decode(SECRET_ADDRESS).unwrap();
+    assert_eq!(prefix, "secret");
+    assert_eq!(data, key().address);
+}
+
+#[test]
+fn verifies_key() {
+    let key = key();
+    key.verify_address().unwrap();
+    assert_eq!(key.address_with_prefix("secret").unwrap(), SECRET_ADDRESS);
+
+    let mut wrong = key.clone();
+    wrong.bech32_address = address::encode("cosmos", &[0; 20]).unwrap();
+    assert!(wrong.verify_address().is_err());
+}
+
+#[test]
+fn verifies_account_data() {
+    let account = AccountData {
+        address: SECRET_ADDRESS.to_string(),
+        algo: Algo::Secp256k1,
+        pubkey: key().pub_key,
+    };
+    account.verify_address().unwrap();
+    assert_eq!(
+        account.address_with_prefix("cosmos").unwrap(),
+        COSMOS_ADDRESS
+    );
+}
+
+#[test]
+fn rejects_invalid_input() {
+    assert!(address::decode("cosmos1invalid").is_err());
+    assert!(address::pubkey_to_address(Algo::Secp256k1, &[1, 2, 3]).is_err());
+    assert!(address::pubkey_to_address(Algo::Sr25519, &[0; 32]).is_err());
+}
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
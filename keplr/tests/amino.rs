@@ -0,0 +1,326 @@
+#![cfg(feature = "cosmrs")]
+
+use cosmrs::{
+    proto::cosmos::{
+        bank::v1beta1::MsgSend,
+        base::v1beta1::Coin,
+        distribution::v1beta1::{
+            MsgFundCommunityPool, MsgSetWithdrawAddress, MsgWithdrawDelegatorReward,
+            MsgWithdrawValidatorCommission,
+        },
+        gov::v1beta1::{MsgDeposit, MsgVote},
+    },
+    proto::cosmwasm::wasm::v1::MsgExecuteContract,
+    Any,
+};
+use keplr::{
+    address,
+    amino::{AminoRegistry, ProtoConverter},
+    proto::{ibc_transfer, secret_compute},
+    sign_types::AminoMsg,
+};
+use serde_json::json;
+
+fn coin(amount: &str) -> Coin {
+    Coin {
+        denom: "uscrt".to_string(),
+        amount: amount.to_string(),
+    }
+}
+
+#[test]
+fn converts_msg_send() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&MsgSend {
+        from_address: "secret1from".to_string(),
+        to_address: "secret1to".to_string(),
+        amount: vec![coin("1000")],
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "cosmos-sdk/MsgSend");
+    assert_eq!(
+        amino.value,
+        json!({
+            "from_address": "secret1from",
+            "to_address": "secret1to",
+            "amount": [{ "denom": "uscrt", "amount": "1000" }],
+        })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn converts_msg_execute_contract() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&MsgExecuteContract {
+        sender: "cosmos1sender".to_string(),
+        contract: "cosmos1contract".to_string(),
+        msg: br#"{"transfer":{"amount":"5"}}"#.to_vec(),
+        funds: vec![],
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "wasm/MsgExecuteContract");
+    assert_eq!(amino.value["msg"], json!({ "transfer": { "amount": "5" } }));
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn rejects_unknown_messages() {
+    let registry = AminoRegistry::empty();
+    let msg = Any {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: vec![],
+    };
+    assert!(registry.to_amino(&msg).is_err());
+}
+
+#[test]
+fn replaces_the_amino_type_of_a_re_registered_message() {
+    let mut registry = AminoRegistry::default();
+    registry.register(ProtoConverter::<MsgSend>::new(
+        "custom/MsgSend",
+        |msg| Ok(json!({ "from": msg.from_address })),
+        |value| {
+            Ok(MsgSend {
+                from_address: value["from"].as_str().unwrap_or_default().to_string(),
+                ..Default::default()
+            })
+        },
+    ));
+    let msg = Any::from_msg(&MsgSend {
+        from_address: "secret1from".to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "custom/MsgSend");
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+
+    let stale = AminoMsg {
+        type_: "cosmos-sdk/MsgSend".to_string(),
+        value: amino.value,
+    };
+    assert!(registry.to_proto(&stale).is_err());
+}
+
+#[test]
+fn converts_msg_vote() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&MsgVote {
+        proposal_id: 42,
+        voter: "cosmos1voter".to_string(),
+        option: 1,
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "cosmos-sdk/MsgVote");
+    assert_eq!(
+        amino.value,
+        json!({ "proposal_id": "42", "voter": "cosmos1voter", "option": 1 })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn omits_default_msg_transfer_fields() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&ibc_transfer::MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: "channel-0".to_string(),
+        token: Some(coin("1")),
+        sender: "secret1from".to_string(),
+        receiver: "osmo1to".to_string(),
+        timeout_height: Some(ibc_transfer::Height {
+            revision_number: 0,
+            revision_height: 100,
+        }),
+        timeout_timestamp: 0,
+        memo: String::new(),
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "cosmos-sdk/MsgTransfer");
+    assert_eq!(
+        amino.value,
+        json!({
+            "source_port": "transfer",
+            "source_channel": "channel-0",
+            "token": { "denom": "uscrt", "amount": "1" },
+            "sender": "secret1from",
+            "receiver": "osmo1to",
+            "timeout_height": { "revision_height": "100" },
+        })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn reads_an_empty_msg_transfer_timeout_height_as_none() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&ibc_transfer::MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: "channel-0".to_string(),
+        token: Some(coin("1")),
+        sender: "secret1from".to_string(),
+        receiver: "osmo1to".to_string(),
+        timeout_height: None,
+        timeout_timestamp: 1_700_000_000_000_000_000,
+        memo: "gm".to_string(),
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.value["timeout_height"], json!({}));
+    assert_eq!(amino.value["timeout_timestamp"], "1700000000000000000");
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+
+    let mut zero = amino.clone();
+    zero.value["timeout_height"] = json!({ "revision_number": "0", "revision_height": "0" });
+    assert_eq!(registry.to_proto(&zero).unwrap(), msg);
+}
+
+#[test]
+fn converts_distribution_messages() {
+    let registry = AminoRegistry::default();
+    let cases = [
+        (
+            Any::from_msg(&MsgWithdrawDelegatorReward {
+                delegator_address: "cosmos1delegator".to_string(),
+                validator_address: "cosmosvaloper1validator".to_string(),
+            })
+            .unwrap(),
+            "cosmos-sdk/MsgWithdrawDelegationReward",
+            json!({
+                "delegator_address": "cosmos1delegator",
+                "validator_address": "cosmosvaloper1validator",
+            }),
+        ),
+        (
+            Any::from_msg(&MsgSetWithdrawAddress {
+                delegator_address: "cosmos1delegator".to_string(),
+                withdraw_address: "cosmos1withdraw".to_string(),
+            })
+            .unwrap(),
+            "cosmos-sdk/MsgModifyWithdrawAddress",
+            json!({
+                "delegator_address": "cosmos1delegator",
+                "withdraw_address": "cosmos1withdraw",
+            }),
+        ),
+        (
+            Any::from_msg(&MsgWithdrawValidatorCommission {
+                validator_address: "cosmosvaloper1validator".to_string(),
+            })
+            .unwrap(),
+            "cosmos-sdk/MsgWithdrawValCommission",
+            json!({ "validator_address": "cosmosvaloper1validator" }),
+        ),
+        (
+            Any::from_msg(&MsgFundCommunityPool {
+                amount: vec![coin("7")],
+                depositor: "cosmos1depositor".to_string(),
+            })
+            .unwrap(),
+            "cosmos-sdk/MsgFundCommunityPool",
+            json!({
+                "amount": [{ "denom": "uscrt", "amount": "7" }],
+                "depositor": "cosmos1depositor",
+            }),
+        ),
+    ];
+
+    for (msg, type_, value) in cases {
+        let amino = registry.to_amino(&msg).unwrap();
+        assert_eq!(amino.type_, type_);
+        assert_eq!(amino.value, value);
+        assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+    }
+}
+
+#[test]
+fn converts_msg_deposit() {
+    let registry = AminoRegistry::default();
+    let msg = Any::from_msg(&MsgDeposit {
+        proposal_id: 7,
+        depositor: "cosmos1depositor".to_string(),
+        amount: vec![coin("10")],
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "cosmos-sdk/MsgDeposit");
+    assert_eq!(
+        amino.value,
+        json!({
+            "proposal_id": "7",
+            "depositor": "cosmos1depositor",
+            "amount": [{ "denom": "uscrt", "amount": "10" }],
+        })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn converts_secret_instantiate_contract() {
+    let sender = "secret1h806c7khnvmjlywdrkdgk2vrayy2mmvf8xjlhl";
+    let registry = AminoRegistry::secret();
+    let msg = Any::from_msg(&secret_compute::MsgInstantiateContract {
+        sender: address::decode(sender).unwrap().1,
+        code_id: 12,
+        label: "my token".to_string(),
+        init_msg: vec![4, 5, 6],
+        init_funds: vec![coin("5")],
+        admin: sender.to_string(),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "wasm/MsgInstantiateContract");
+    assert_eq!(
+        amino.value,
+        json!({
+            "sender": sender,
+            "code_id": "12",
+            "label": "my token",
+            "init_msg": "BAUG",
+            "init_funds": [{ "denom": "uscrt", "amount": "5" }],
+            "admin": sender,
+        })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
+
+#[test]
+fn converts_secret_execute_contract() {
+    let sender = "secret1h806c7khnvmjlywdrkdgk2vrayy2mmvf8xjlhl";
+    let registry = AminoRegistry::secret();
+    let msg = Any::from_msg(&secret_compute::MsgExecuteContract {
+        sender: address::decode(sender).unwrap().1,
+        contract: address::decode(sender).unwrap().1,
+        msg: vec![1, 2, 3],
+        sent_funds: vec![coin("5")],
+        ..Default::default()
+    })
+    .unwrap();
+
+    let amino = registry.to_amino(&msg).unwrap();
+    assert_eq!(amino.type_, "wasm/MsgExecuteContract");
+    assert_eq!(
+        amino.value,
+        json!({
+            "sender": sender,
+            "contract": sender,
+            "msg": "AQID",
+            "sent_funds": [{ "denom": "uscrt", "amount": "5" }],
+        })
+    );
+    assert_eq!(registry.to_proto(&amino).unwrap(), msg);
+}
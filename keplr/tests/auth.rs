@@ -0,0 +1,105 @@
+#![cfg(feature = "auth")]
+
+use keplr::{
+    auth::{get_account, Account},
+    lcd::Lcd,
+    Error,
+};
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const ADDRESS: &str = "secret1h806c7khnvmjlywdrkdgk2vrayy2mmvf8xjlhl";
+
+fn base_account() -> Value {
+    json!({
+        "address": ADDRESS,
+        "pub_key": null,
+        "account_number": "1234",
+        "sequence": "56",
+    })
+}
+
+async fn lcd(account: Value) -> (MockServer, Lcd) {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!("/cosmos/auth/v1beta1/accounts/{ADDRESS}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "account": account })))
+        .mount(&server)
+        .await;
+    let lcd = Lcd::new(server.uri());
+    (server, lcd)
+}
+
+fn expected(type_url: &str) -> Account {
+    Account {
+        type_url: type_url.to_string(),
+        address: ADDRESS.to_string(),
+        account_number: 1234,
+        sequence: 56,
+    }
+}
+
+#[tokio::test]
+async fn reads_base_accounts() {
+    let mut account = base_account();
+    account["@type"] = json!("/cosmos.auth.v1beta1.BaseAccount");
+    let (_server, lcd) = lcd(account).await;
+    assert_eq!(
+        get_account(&lcd, ADDRESS).await.unwrap(),
+        expected("/cosmos.auth.v1beta1.BaseAccount")
+    );
+}
+
+#[tokio::test]
+async fn reads_vesting_accounts() {
+    let (_server, lcd) = lcd(json!({
+        "@type": "/cosmos.vesting.v1beta1.ContinuousVestingAccount",
+        "base_vesting_account": {
+            "base_account": base_account(),
+            "original_vesting": [{ "denom": "uscrt", "amount": "1000" }],
+            "delegated_free": [],
+            "delegated_vesting": [],
+            "end_time": "1700000000",
+        },
+        "start_time": "1600000000",
+    }))
+    .await;
+    assert_eq!(
+        get_account(&lcd, ADDRESS).await.unwrap(),
+        expected("/cosmos.vesting.v1beta1.ContinuousVestingAccount")
+    );
+}
+
+#[tokio::test]
+async fn reads_eth_accounts() {
+    let (_server, lcd) = lcd(json!({
+        "@type": "/ethermint.types.v1.EthAccount",
+        "base_account": base_account(),
+        "code_hash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+    }))
+    .await;
+    assert_eq!(
+        get_account(&lcd, ADDRESS).await.unwrap(),
+        expected("/ethermint.types.v1.EthAccount")
+    );
+}
+
+#[tokio::test]
+async fn reports_missing_accounts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "code": 5,
+            "message": format!("account {ADDRESS} not found"),
+            "details": [],
+        })))
+        .mount(&server)
+        .await;
+    assert!(matches!(
+        get_account(&Lcd::new(server.uri()), ADDRESS).await,
+        Err(Error::Request(message)) if message.contains("not found")
+    ));
+}
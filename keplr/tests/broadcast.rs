@@ -0,0 +1,286 @@
+#![cfg(feature = "broadcast")]
+
+use keplr::{
+    broadcast::{tx_hash, wait_for_tx},
+    lcd::Lcd,
+    rpc::Rpc,
+    Error,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const HASH: &str = "0A1B2C";
+
+fn tx_response(code: u32, raw_log: &str) -> Value {
+    json!({
+        "tx": null,
+        "tx_response": {
+            "height": "123",
+            "txhash": HASH,
+            "codespace": if code == 0 { "" } else { "sdk" },
+            "code": code,
+            "data": "",
+            "raw_log": raw_log,
+            "logs": [],
+            "info": "",
+            "gas_wanted": "200000",
+            "gas_used": "81234",
+            "tx": null,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "events": [{
+                "type": "instantiate",
+                "attributes": [
+                    { "key": "code_id", "value": "7", "index": true },
+                    { "key": "_contract_address", "value": "secret1contract", "index": true },
+                ],
+            }],
+        },
+    })
+}
+
+async fn lcd(pending: u64, response: Value) -> (MockServer, Lcd) {
+    let server = MockServer::start().await;
+    let tx_path = format!("/cosmos/tx/v1beta1/txs/{HASH}");
+    Mock::given(method("GET"))
+        .and(path(tx_path.clone()))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "code": 5,
+            "message": format!("tx not found: {HASH}"),
+        })))
+        .up_to_n_times(pending)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(tx_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(&server)
+        .await;
+    let lcd = Lcd::new(server.uri());
+    (server, lcd)
+}
+
+#[test]
+fn formats_tx_hash() {
+    assert_eq!(tx_hash(&[0x0a, 0x1b, 0x2c]), HASH);
+}
+
+#[tokio::test]
+async fn waits_for_inclusion() {
+    let (_server, lcd) = lcd(2, tx_response(0, "")).await;
+    let response = wait_for_tx(&lcd, HASH, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(response.height, 123);
+    assert_eq!(response.gas_used, 81_234);
+    assert_eq!(
+        response.find_attribute("instantiate", "_contract_address"),
+        Some("secret1contract")
+    );
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let server = MockServer::start().await;
+    let tx_path = format!("/cosmos/tx/v1beta1/txs/{HASH}");
+    Mock::given(method("GET"))
+        .and(path(tx_path.clone()))
+        .respond_with(ResponseTemplate::new(503).set_body_string("upstream unavailable"))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(tx_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tx_response(0, "")))
+        .mount(&server)
+        .await;
+
+    let lcd = Lcd::new(server.uri());
+    let response = wait_for_tx(&lcd, HASH, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(response.txhash, HASH);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn retries_unreachable_nodes_until_the_deadline() {
+    let server = MockServer::start().await;
+    let lcd = Lcd::new(server.uri());
+    drop(server);
+
+    assert!(matches!(
+        wait_for_tx(&lcd, HASH, Duration::from_millis(800)).await,
+        Err(Error::Timeout(_))
+    ));
+}
+
+#[tokio::test]
+async fn fails_on_client_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "code": 3,
+            "message": "invalid tx hash",
+        })))
+        .mount(&server)
+        .await;
+
+    let lcd = Lcd::new(server.uri());
+    assert_eq!(
+        wait_for_tx(&lcd, HASH, Duration::from_secs(10)).await,
+        Err(Error::Request(
+            "400 Bad Request: invalid tx hash".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn reports_failed_transactions() {
+    let (_server, lcd) = lcd(1, tx_response(5, "insufficient funds")).await;
+    assert_eq!(
+        wait_for_tx(&lcd, HASH, Duration::from_secs(10)).await,
+        Err(Error::TxFailed {
+            hash: HASH.to_string(),
+            codespace: "sdk".to_string(),
+            code: 5,
+            log: "insufficient funds".to_string(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn times_out() {
+    let (_server, lcd) = lcd(u64::MAX, tx_response(0, "")).await;
+    assert!(matches!(
+        wait_for_tx(&lcd, HASH, Duration::from_millis(800)).await,
+        Err(Error::Timeout(_))
+    ));
+}
+
+fn rpc_tx(code: u32, log: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": -1,
+        "result": {
+            "hash": HASH,
+            "height": "123",
+            "index": 0,
+            "tx_result": {
+                "code": code,
+                "data": "CgQKAggB",
+                "log": log,
+                "info": "",
+                "gas_wanted": "200000",
+                "gas_used": "81234",
+                "events": [{
+                    "type": "instantiate",
+                    "attributes": [
+                        { "key": "code_id", "value": "7", "index": true },
+                        { "key": "_contract_address", "value": "secret1contract", "index": true },
+                    ],
+                }],
+                "codespace": if code == 0 { "" } else { "sdk" },
+            },
+            "tx": "CpIBCo8BChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5k",
+        },
+    })
+}
+
+fn rpc_error(data: &str) -> ResponseTemplate {
+    ResponseTemplate::new(500).set_body_json(json!({
+        "jsonrpc": "2.0",
+        "id": -1,
+        "error": { "code": -32603, "message": "Internal error", "data": data },
+    }))
+}
+
+async fn rpc(pending: u64, response: Value) -> (MockServer, Rpc) {
+    let server = MockServer::start().await;
+    let hash = format!("0x{HASH}");
+    Mock::given(method("GET"))
+        .and(path("/tx"))
+        .and(query_param("hash", hash.as_str()))
+        .respond_with(rpc_error(&format!("tx ({HASH}) not found")))
+        .up_to_n_times(pending)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/tx"))
+        .and(query_param("hash", hash.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(&server)
+        .await;
+    let rpc = Rpc::new(server.uri());
+    (server, rpc)
+}
+
+#[tokio::test]
+async fn waits_for_inclusion_over_rpc() {
+    let (server, rpc) = rpc(2, rpc_tx(0, "")).await;
+    let response = wait_for_tx(&rpc, HASH, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(response.txhash, HASH);
+    assert_eq!(response.height, 123);
+    assert_eq!(response.gas_wanted, 200_000);
+    assert_eq!(response.gas_used, 81_234);
+    assert_eq!(response.data, "0A040A020801");
+    assert_eq!(
+        response.find_attribute("instantiate", "_contract_address"),
+        Some("secret1contract")
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn reports_failed_transactions_over_rpc() {
+    let (_server, rpc) = rpc(1, rpc_tx(5, "insufficient funds")).await;
+    assert_eq!(
+        wait_for_tx(&rpc, HASH, Duration::from_secs(10)).await,
+        Err(Error::TxFailed {
+            hash: HASH.to_string(),
+            codespace: "sdk".to_string(),
+            code: 5,
+            log: "insufficient funds".to_string(),
+        })
+    );
+}
+
+#[tokio::test]
+async fn fails_on_rpc_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(rpc_error("error converting http params to arguments"))
+        .mount(&server)
+        .await;
+
+    let rpc = Rpc::new(server.uri());
+    assert_eq!(
+        wait_for_tx(&rpc, HASH, Duration::from_secs(10)).await,
+        Err(Error::Request(
+            "Internal error: error converting http params to arguments".to_string()
+        ))
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_rpc_proxy_errors() {
+    let (server, rpc) = rpc(1, rpc_tx(0, "")).await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("<html>Bad Gateway</html>"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+
+    let response = wait_for_tx(&rpc, HASH, Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert_eq!(response.txhash, HASH);
+    assert_eq!(server.received_requests().await.unwrap().len(), 3);
+}
@@ -0,0 +1,171 @@
+use keplr::{
+    chain_info::{ChainInfoBuilder, ChainInfoError},
+    suggest_chain_types::{Currency, GasPriceStep},
+};
+
+fn scrt() -> Currency {
+    Currency::new("SCRT", "uscrt", 6)
+}
+
+fn gas() -> GasPriceStep {
+    GasPriceStep {
+        low: 0.1,
+        average: 0.25,
+        high: 0.5,
+    }
+}
+
+fn localsecret() -> ChainInfoBuilder {
+    ChainInfoBuilder::new("secretdev-1")
+        .chain_name("LocalSecret")
+        .rpc("http://localhost:26657")
+        .rest("http://localhost:1317")
+        .bip44(529)
+        .bech32_prefix("secret")
+        .stake_currency(scrt())
+        .fee_currency(scrt(), gas())
+}
+
+#[test]
+fn builds_chain_info() {
+    let chain_info = localsecret().build().unwrap();
+    assert_eq!(chain_info.chain_id, "secretdev-1");
+    assert_eq!(chain_info.chain_name, "LocalSecret");
+    assert_eq!(chain_info.rest, "http://localhost:1317");
+    assert_eq!(chain_info.bip44.coin_type, 529);
+    assert_eq!(
+        chain_info.bech32_config.bech32_prefix_cons_pub,
+        "secretvalconspub"
+    );
+    assert_eq!(chain_info.stake_currency, scrt());
+    assert_eq!(chain_info.fee_currencies[0].gas_price_step, gas());
+    // the stake and fee currency are listed once
+    assert_eq!(chain_info.currencies, [scrt()]);
+}
+
+#[test]
+fn defaults_name_and_coin_type() {
+    let chain_info = ChainInfoBuilder::new("mychain-1")
+        .rpc("https://rpc.mychain.zone")
+        .rest("https://lcd.mychain.zone/")
+        .bech32_prefix("my")
+        .currency(Currency::new("MY", "umy", 6))
+        .stake_currency(Currency::new("MY", "umy", 6))
+        .fee_currency(Currency::new("ATOM", "ibc/27394FB", 6), gas())
+        .build()
+        .unwrap();
+    assert_eq!(chain_info.chain_name, "mychain-1");
+    assert_eq!(chain_info.bip44.coin_type, 118);
+    let denoms: Vec<_> = chain_info
+        .currencies
+        .iter()
+        .map(|currency| currency.coin_minimal_denom.as_str())
+        .collect();
+    assert_eq!(denoms, ["umy", "ibc/27394FB"]);
+}
+
+#[test]
+fn reports_every_problem() {
+    let errors = ChainInfoBuilder::new("broken-1")
+        .rpc("localhost:26657")
+        .currency(Currency::new("A", "ua", 6))
+        .currency(Currency::new("A", "ua", 6))
+        .stake_currency(Currency::new("B", "ub", 24))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            ChainInfoError::InvalidUrl {
+                field: "rpc",
+                url: "localhost:26657".to_string()
+            },
+            ChainInfoError::Missing("rest"),
+            ChainInfoError::Missing("bech32_prefix"),
+            ChainInfoError::DuplicateDenom("ua".to_string()),
+            ChainInfoError::MissingFeeCurrency,
+            ChainInfoError::InvalidDecimals {
+                denom: "ub".to_string(),
+                decimals: 24
+            },
+        ]
+    );
+    assert_eq!(errors[1].to_string(), "rest is required");
+}
+
+#[test]
+fn validates_urls_and_gas_prices() {
+    for url in [
+        "http://",
+        "ftp://lcd.secret.express",
+        "https://lcd secret.express",
+        "https://lcd.secret.express:port",
+        "http://[::1",
+        "http://[::g]:1317",
+        "http://[::1]1317",
+    ] {
+        let errors = localsecret().rest(url).build().unwrap_err();
+        assert_eq!(
+            errors,
+            [ChainInfoError::InvalidUrl {
+                field: "rest",
+                url: url.to_string()
+            }]
+        );
+    }
+    for url in [
+        "https://lcd.secret.express:443/api?x=1",
+        "http://[::1]:1317",
+        "http://[::1]/",
+    ] {
+        assert!(localsecret().rest(url).build().is_ok(), "{url}");
+    }
+
+    let errors = localsecret()
+        .fee_currency(
+            Currency::new("ATOM", "uatom", 6),
+            GasPriceStep {
+                low: 0.5,
+                average: 0.25,
+                high: 0.1,
+            },
+        )
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [ChainInfoError::InvalidGasPriceStep("uatom".to_string())]
+    );
+}
+
+#[test]
+fn reports_conflicting_decimals() {
+    let errors = localsecret()
+        .currency(Currency::new("ATOM", "uatom", 6))
+        .fee_currency(Currency::new("ATOM", "uatom", 18), gas())
+        .stake_currency(Currency::new("SCRT", "uscrt", 18))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        errors,
+        [
+            ChainInfoError::ConflictingDecimals("uatom".to_string()),
+            ChainInfoError::ConflictingDecimals("uscrt".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn requires_a_chain_id() {
+    for chain_id in ["", "  "] {
+        let errors = ChainInfoBuilder::new(chain_id)
+            .rpc("http://localhost:26657")
+            .rest("http://localhost:1317")
+            .bech32_prefix("secret")
+            .fee_currency(scrt(), gas())
+            .stake_currency(scrt())
+            .build()
+            .unwrap_err();
+        assert_eq!(errors, [ChainInfoError::Missing("chain_id")]);
+    }
+}
@@ -0,0 +1,119 @@
+use keplr::{
+    chain_registry::{from_dir, from_json, FeeToken},
+    suggest_chain_types::{Bech32Config, Currency, GasPriceStep},
+    Error,
+};
+
+const SECRET_CHAIN: &str = include_str!("fixtures/chain-registry/secretnetwork/chain.json");
+const SECRET_ASSETS: &str = include_str!("fixtures/chain-registry/secretnetwork/assetlist.json");
+const OSMOSIS_CHAIN: &str = include_str!("fixtures/chain-registry/osmosis/chain.json");
+const OSMOSIS_ASSETS: &str = include_str!("fixtures/chain-registry/osmosis/assetlist.json");
+
+#[test]
+fn converts_secret_network() {
+    let info = from_json(SECRET_CHAIN, SECRET_ASSETS).unwrap();
+    assert_eq!(info.chain_id, "secret-4");
+    assert_eq!(info.chain_name, "Secret Network");
+    assert_eq!(info.rpc, "https://rpc.ankr.com/http/scrt_cosmos");
+    assert_eq!(info.rest, "https://rpc.ankr.com/http/scrt_cosmos");
+    assert_eq!(info.bip44.coin_type, 529);
+    assert_eq!(info.bech32_config, Bech32Config::from_prefix("secret"));
+    assert_eq!(info.bech32_config.bech32_prefix_val_addr, "secretvaloper");
+
+    let scrt = Currency {
+        coin_denom: "SCRT".to_string(),
+        coin_minimal_denom: "uscrt".to_string(),
+        coin_decimals: 6,
+        coin_gecko_id: "secret".to_string(),
+    };
+    // the SNIP-20 token is left out
+    assert_eq!(info.stake_currency, scrt);
+    assert_eq!(info.currencies, [scrt]);
+
+    assert_eq!(info.fee_currencies.len(), 1);
+    assert_eq!(info.fee_currencies[0].coin_minimal_denom, "uscrt");
+    assert_eq!(
+        info.fee_currencies[0].gas_price_step,
+        GasPriceStep {
+            low: 0.1,
+            average: 0.25,
+            high: 0.5
+        }
+    );
+}
+
+#[test]
+fn converts_ibc_fee_tokens() {
+    let info = from_json(OSMOSIS_CHAIN, OSMOSIS_ASSETS).unwrap();
+    assert_eq!(info.chain_id, "osmosis-1");
+    assert_eq!(info.bech32_config.bech32_prefix_acc_pub, "osmopub");
+    assert_eq!(info.currencies.len(), 2);
+
+    let atom = &info.fee_currencies[1];
+    assert_eq!(atom.coin_denom, "ATOM");
+    assert!(atom.coin_minimal_denom.starts_with("ibc/"));
+    assert_eq!(atom.coin_decimals, 6);
+    assert_eq!(
+        atom.gas_price_step,
+        GasPriceStep {
+            low: 0.0025,
+            average: 0.025,
+            high: 0.04
+        }
+    );
+}
+
+#[test]
+fn fills_in_missing_gas_prices() {
+    let token = FeeToken {
+        denom: "uscrt".to_string(),
+        fixed_min_gas_price: Some(0.05),
+        ..Default::default()
+    };
+    let step = token.gas_price_step();
+    assert_eq!((step.low, step.average, step.high), (0.05, 0.05, 0.05));
+
+    let token = FeeToken {
+        denom: "uatom".to_string(),
+        ..Default::default()
+    };
+    let step = token.gas_price_step();
+    assert_eq!((step.low, step.average, step.high), (0.01, 0.025, 0.04));
+}
+
+#[test]
+fn rejects_mismatched_or_incomplete_files() {
+    let error = from_json(SECRET_CHAIN, OSMOSIS_ASSETS).unwrap_err();
+    assert_eq!(
+        error,
+        Error::ChainRegistry("the asset list is for osmosis, not secretnetwork".to_string())
+    );
+
+    let no_rest = SECRET_CHAIN.replace("\"rest\"", "\"rest_unused\"");
+    let error = from_json(&no_rest, SECRET_ASSETS).unwrap_err();
+    assert_eq!(
+        error,
+        Error::ChainRegistry("secret-4 lists no REST endpoint".to_string())
+    );
+
+    assert!(matches!(
+        from_json("{}", SECRET_ASSETS),
+        Err(Error::ChainRegistry(message)) if message.starts_with("invalid chain.json")
+    ));
+}
+
+#[test]
+fn reads_chain_directories() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/chain-registry");
+    assert_eq!(
+        from_dir(format!("{dir}/secretnetwork")).unwrap(),
+        from_json(SECRET_CHAIN, SECRET_ASSETS).unwrap()
+    );
+
+    let error = from_dir(format!("{dir}/cosmoshub")).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::ChainRegistry(message)
+            if message.starts_with(&format!("failed to read {dir}/cosmoshub/chain.json"))
+    ));
+}
@@ -0,0 +1,136 @@
+#![cfg(any(feature = "yew", feature = "dioxus", feature = "leptos"))]
+
+use keplr::{
+    connection::{ConnectionAction, ConnectionState, ConnectionStatus, Connections},
+    Error, Key,
+};
+
+fn key(name: &str) -> Key {
+    Key {
+        name: name.to_string(),
+        algo: "secp256k1".to_string(),
+        bech32_address: format!("secret1{name}"),
+        ..Default::default()
+    }
+}
+
+fn connected() -> ConnectionState {
+    ConnectionState::default()
+        .apply(ConnectionAction::Connect)
+        .apply(ConnectionAction::Connected(key("alice")))
+}
+
+#[test]
+fn connects() {
+    let connecting = ConnectionState::default().apply(ConnectionAction::Connect);
+    assert_eq!(connecting.status, ConnectionStatus::Connecting);
+    assert!(!connecting.is_connected());
+
+    let state = connected();
+    assert!(state.is_connected());
+    assert_eq!(state.key, Some(key("alice")));
+}
+
+#[test]
+fn failed_connection_keeps_the_error() {
+    let state = ConnectionState::default()
+        .apply(ConnectionAction::Connect)
+        .apply(ConnectionAction::Failed(Error::KeplrUnavailable));
+    assert_eq!(state.status, ConnectionStatus::Disconnected);
+    assert_eq!(state.error, Some(Error::KeplrUnavailable));
+
+    let retry = state.apply(ConnectionAction::Connect);
+    assert_eq!(retry.error, None);
+}
+
+#[test]
+fn follows_account_switches_only_while_connected() {
+    let state = connected().apply(ConnectionAction::KeyChanged(key("bob")));
+    assert_eq!(state.key, Some(key("bob")));
+
+    let state = state
+        .apply(ConnectionAction::Disconnected)
+        .apply(ConnectionAction::KeyChanged(key("carol")));
+    assert_eq!(state, ConnectionState::default());
+}
+
+#[test]
+fn failed_refresh_stays_connected() {
+    let state = connected().apply(ConnectionAction::Failed(Error::JavaScript(
+        "Request rejected".to_string(),
+    )));
+    assert!(state.is_connected());
+    assert_eq!(state.key, Some(key("alice")));
+    assert!(state.error.is_some());
+}
+
+#[test]
+fn tracks_each_chain_separately() {
+    let mut connections = Connections::default();
+    for chain_id in ["secret-4", "osmosis-1"] {
+        connections.apply(chain_id, ConnectionAction::Connect);
+    }
+    assert_eq!(
+        connections.state("osmosis-1").status,
+        ConnectionStatus::Connecting
+    );
+
+    connections.apply("secret-4", ConnectionAction::Connected(key("alice")));
+    connections.apply(
+        "osmosis-1",
+        ConnectionAction::Failed(Error::JavaScript(
+            "There is no chain info for osmosis-1".to_string(),
+        )),
+    );
+    assert_eq!(connections.key("secret-4"), Some(&key("alice")));
+    assert_eq!(connections.connected().collect::<Vec<_>>(), ["secret-4"]);
+    assert_eq!(
+        connections.state("osmosis-1").status,
+        ConnectionStatus::Disconnected
+    );
+    assert!(connections.state("osmosis-1").error.is_some());
+    assert_eq!(connections.state("cosmoshub-4"), ConnectionState::default());
+}
+
+#[test]
+fn refreshes_each_chain_independently() {
+    let mut connections = Connections::default();
+    for chain_id in ["secret-4", "osmosis-1"] {
+        connections.apply(chain_id, ConnectionAction::Connect);
+        connections.apply(chain_id, ConnectionAction::Connected(key("alice")));
+    }
+
+    connections.apply(
+        "secret-4",
+        ConnectionAction::Failed(Error::Timeout("no response".to_string())),
+    );
+    connections.apply("osmosis-1", ConnectionAction::KeyChanged(key("bob")));
+
+    let secret = connections.state("secret-4");
+    assert!(secret.is_connected());
+    assert_eq!(secret.key, Some(key("alice")));
+    assert_eq!(
+        secret.error,
+        Some(Error::Timeout("no response".to_string()))
+    );
+    assert_eq!(connections.key("osmosis-1"), Some(&key("bob")));
+    assert_eq!(connections.state("osmosis-1").error, None);
+    assert_eq!(
+        connections.connected().collect::<Vec<_>>(),
+        ["osmosis-1", "secret-4"]
+    );
+}
+
+#[test]
+fn forgets_disconnected_chains() {
+    let mut connections = Connections::default();
+    connections.apply("secret-4", ConnectionAction::Connect);
+    connections.apply("secret-4", ConnectionAction::Connected(key("alice")));
+
+    connections.apply("secret-4", ConnectionAction::Disconnected);
+    assert_eq!(connections, Connections::default());
+
+    // a refresh finishing after the disconnect doesn't bring the chain back
+    connections.apply("secret-4", ConnectionAction::KeyChanged(key("bob")));
+    assert_eq!(connections, Connections::default());
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "cosmrs")]
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use cosmrs::tx;
+use keplr::{sign_types::*, AccountData, Algo};
+
+#[test]
+fn converts_sign_docs() {
+    let sign_doc = tx::SignDoc {
+        body_bytes: vec![1, 2, 3],
+        auth_info_bytes: vec![4, 5],
+        chain_id: "secret-4".to_string(),
+        account_number: 12345,
+    };
+    let converted = SignDoc::from(sign_doc.clone());
+    assert_eq!(converted.account_number, "12345");
+    assert_eq!(tx::SignDoc::try_from(converted).unwrap(), sign_doc);
+}
+
+#[test]
+fn assembles_raw_tx_from_response() {
+    let response = DirectSignResponse {
+        signed: SignDoc {
+            body_bytes: vec![1, 2, 3],
+            auth_info_bytes: vec![4, 5],
+            chain_id: "secret-4".to_string(),
+            account_number: "1".to_string(),
+        },
+        signature: StdSignature {
+            pub_key: PubKey {
+                type_: "tendermint/PubKeySecp256k1".to_string(),
+                value: String::new(),
+            },
+            signature: BASE64_STANDARD.encode([9; 64]),
+        },
+    };
+    let raw = tx::Raw::try_from(response).unwrap();
+    let tx_raw = raw.to_bytes().unwrap();
+    let decoded: cosmrs::proto::cosmos::tx::v1beta1::TxRaw =
+        cosmrs::proto::prost::Message::decode(tx_raw.as_slice()).unwrap();
+    assert_eq!(decoded.body_bytes, vec![1, 2, 3]);
+    assert_eq!(decoded.auth_info_bytes, vec![4, 5]);
+    assert_eq!(decoded.signatures, vec![vec![9; 64]]);
+}
+
+#[test]
+fn converts_account_public_key() {
+    let account = AccountData {
+        address: "cosmos1h806c7khnvmjlywdrkdgk2vrayy2mmvf9rxk2r".to_string(),
+        algo: Algo::Secp256k1,
+        pubkey: BASE64_STANDARD
+            .decode("AtQaCqFnshaZQp6rIkvAPyzThvCvXSDO+9AzbxVErqJP")
+            .unwrap(),
+    };
+    let public_key = account.public_key().unwrap();
+    assert_eq!(
+        public_key.account_id("cosmos").unwrap().to_string(),
+        account.address
+    );
+    assert_eq!(public_key.to_bytes(), account.pubkey);
+}
@@ -0,0 +1,170 @@
+use futures::executor::block_on;
+use keplr::{
+    enable_or_suggest_with,
+    suggest_chain_types::{
+        Bech32Config, Bip44, Currency, FeeCurrency, GasPriceStep, SuggestingChainInfo,
+    },
+    EnableReport, Error,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+};
+
+fn chain_info(chain_id: &str) -> SuggestingChainInfo {
+    let currency = Currency::new("SCRT", "uscrt", 6);
+    SuggestingChainInfo {
+        chain_id: chain_id.to_string(),
+        chain_name: chain_id.to_string(),
+        rpc: "http://localhost:26657".to_string(),
+        rest: "http://localhost:1317".to_string(),
+        bip44: Bip44 { coin_type: 529 },
+        bech32_config: Bech32Config::from_prefix("secret"),
+        currencies: vec![currency.clone()],
+        fee_currencies: vec![FeeCurrency::new(
+            currency.clone(),
+            GasPriceStep {
+                low: 0.1,
+                average: 0.25,
+                high: 0.5,
+            },
+        )],
+        stake_currency: currency,
+    }
+}
+
+fn chains(chain_ids: &[&str]) -> BTreeMap<String, SuggestingChainInfo> {
+    chain_ids
+        .iter()
+        .map(|chain_id| (chain_id.to_string(), chain_info(chain_id)))
+        .collect()
+}
+
+/// A wallet that knows some chains and fails on the first unknown one, like Keplr.
+#[derive(Default)]
+struct FakeWallet {
+    known: RefCell<BTreeSet<String>>,
+    /// Chains the user refuses to add.
+    refused: BTreeSet<String>,
+    /// Fails every `enable` with this error instead.
+    error: Option<Error>,
+    requests: RefCell<Vec<String>>,
+}
+
+impl FakeWallet {
+    fn knowing(chain_ids: &[&str]) -> Self {
+        Self {
+            known: RefCell::new(chain_ids.iter().map(|id| id.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    async fn enable(&self, chain_ids: Vec<String>) -> Result<(), Error> {
+        self.requests
+            .borrow_mut()
+            .push(format!("enable {}", chain_ids.join(",")));
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        match chain_ids
+            .iter()
+            .find(|id| !self.known.borrow().contains(*id))
+        {
+            Some(unknown) => Err(Error::JavaScript(format!(
+                "There is no chain info for {unknown}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    async fn suggest(&self, chain_info: &SuggestingChainInfo) -> Result<(), Error> {
+        let chain_id = &chain_info.chain_id;
+        self.requests
+            .borrow_mut()
+            .push(format!("suggest {chain_id}"));
+        if self.refused.contains(chain_id) {
+            return Err(Error::JavaScript("Request rejected".to_string()));
+        }
+        self.known.borrow_mut().insert(chain_id.clone());
+        Ok(())
+    }
+
+    fn run(&self, chains: &BTreeMap<String, SuggestingChainInfo>) -> EnableReport {
+        block_on(enable_or_suggest_with(
+            chains,
+            |chain_ids| self.enable(chain_ids),
+            |chain_info| self.suggest(chain_info),
+        ))
+    }
+}
+
+#[test]
+fn suggests_unknown_chains_and_retries() {
+    let wallet = FakeWallet::knowing(&["secret-4"]);
+    let report = wallet.run(&chains(&["secret-4", "secretdev-1"]));
+
+    assert!(report.is_complete());
+    assert_eq!(report.enabled, ["secret-4", "secretdev-1"]);
+    assert_eq!(report.suggested, ["secretdev-1"]);
+    assert_eq!(
+        *wallet.requests.borrow(),
+        [
+            "enable secret-4,secretdev-1",
+            "suggest secretdev-1",
+            "enable secret-4,secretdev-1",
+        ]
+    );
+}
+
+#[test]
+fn failed_suggestions_do_not_block_other_chains() {
+    let wallet = FakeWallet {
+        refused: ["pulsar-3".to_string()].into(),
+        ..FakeWallet::knowing(&["secret-4"])
+    };
+    let report = wallet.run(&chains(&["pulsar-3", "secret-4", "secretdev-1"]));
+
+    assert!(!report.is_complete());
+    assert_eq!(report.enabled, ["secret-4", "secretdev-1"]);
+    assert_eq!(report.suggested, ["secretdev-1"]);
+    assert_eq!(
+        report.failed,
+        BTreeMap::from([(
+            "pulsar-3".to_string(),
+            Error::JavaScript("Request rejected".to_string())
+        )])
+    );
+}
+
+#[test]
+fn other_errors_fail_all_pending_chains() {
+    let error = Error::Timeout("no response".to_string());
+    let wallet = FakeWallet {
+        error: Some(error.clone()),
+        ..FakeWallet::knowing(&["secret-4"])
+    };
+    let report = wallet.run(&chains(&["secret-4", "secretdev-1"]));
+
+    assert!(report.enabled.is_empty());
+    assert!(report.suggested.is_empty());
+    assert_eq!(
+        report.failed,
+        BTreeMap::from([
+            ("secret-4".to_string(), error.clone()),
+            ("secretdev-1".to_string(), error),
+        ])
+    );
+    assert_eq!(wallet.requests.borrow().len(), 1);
+}
+
+#[test]
+fn rejects_chain_info_under_another_chain_id() {
+    let wallet = FakeWallet::knowing(&["secret-4"]);
+    let mut chains = chains(&["secret-4"]);
+    chains.insert("pulsar-3".to_string(), chain_info("secretdev-1"));
+    let report = wallet.run(&chains);
+
+    assert_eq!(report.enabled, ["secret-4"]);
+    assert!(matches!(report.failed["pulsar-3"], Error::Request(_)));
+    assert_eq!(*wallet.requests.borrow(), ["enable secret-4"]);
+}
@@ -0,0 +1,97 @@
+use keplr::{AccountData, Algo, Key};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Raw {
+    #[serde(with = "keplr::encoding::uint8array")]
+    bytes: Vec<u8>,
+}
+
+fn key() -> Key {
+    Key {
+        name: "test".to_string(),
+        algo: "secp256k1".to_string(),
+        pub_key: vec![2, 170, 187, 204],
+        address: vec![0, 1, 2, 3, 255],
+        bech32_address: "secret1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnplylyg".to_string(),
+        ethereum_hex_address: String::new(),
+        is_nano_ledger: false,
+        is_keystone: false,
+    }
+}
+
+#[test]
+fn key_round_trips_through_json_as_base64() {
+    let key = key();
+    let value = serde_json::to_value(&key).unwrap();
+    assert_eq!(value["pubKey"], json!("Aqq7zA=="));
+    assert_eq!(value["address"], json!("AAECA/8="));
+
+    let decoded: Key = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded, key);
+}
+
+#[test]
+fn key_accepts_byte_arrays() {
+    let mut value = serde_json::to_value(key()).unwrap();
+    value["pubKey"] = json!([2, 170, 187, 204]);
+    value["address"] = json!([0, 1, 2, 3, 255]);
+
+    let decoded: Key = serde_json::from_value(value).unwrap();
+    assert_eq!(decoded, key());
+}
+
+#[test]
+fn account_data_round_trips_through_json() {
+    let account = AccountData {
+        address: "secret1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnplylyg".to_string(),
+        algo: Algo::Secp256k1,
+        pubkey: vec![3; 33],
+    };
+    let json = serde_json::to_string(&account).unwrap();
+    assert_eq!(serde_json::from_str::<AccountData>(&json).unwrap(), account);
+}
+
+#[test]
+fn algo_keeps_its_serialized_form() {
+    assert_eq!(json!(Algo::Secp256k1), json!("Secp256k1"));
+    assert_eq!(json!(Algo::Ethsecp256k1), json!("ethsecp256k1"));
+    for (name, algo) in [
+        ("Secp256k1", Algo::Secp256k1),
+        ("secp256k1", Algo::Secp256k1),
+        ("ethsecp256k1", Algo::Ethsecp256k1),
+        ("ed25519", Algo::Ed25519),
+        ("Sr25519", Algo::Sr25519),
+    ] {
+        assert_eq!(serde_json::from_value::<Algo>(json!(name)).unwrap(), algo);
+    }
+}
+
+#[test]
+fn uint8array_round_trips() {
+    let raw = Raw {
+        bytes: vec![0, 127, 128, 255],
+    };
+    let json = serde_json::to_string(&raw).unwrap();
+    assert_eq!(json, r#"{"bytes":[0,127,128,255]}"#);
+    assert_eq!(serde_json::from_str::<Raw>(&json).unwrap(), raw);
+    assert_eq!(
+        serde_json::from_str::<Raw>(r#"{"bytes":"AH+A/w=="}"#).unwrap(),
+        raw
+    );
+}
+
+#[test]
+fn invalid_base64_is_rejected() {
+    assert!(serde_json::from_str::<Raw>(r#"{"bytes":"not base64!"}"#).is_err());
+}
+
+#[test]
+fn sorts_json_keys_recursively() {
+    let value = json!({ "b": 1, "a": { "d": [{ "f": 1, "e": 2 }], "c": null } });
+    assert_eq!(
+        keplr::encoding::to_sorted_json(&value).unwrap(),
+        r#"{"a":{"c":null,"d":[{"e":2,"f":1}]},"b":1}"#
+    );
+}
@@ -0,0 +1,56 @@
+//! The `uint8array` adapter against real JS values, run with `wasm-pack test --node`.
+
+#![cfg(target_arch = "wasm32")]
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::wasm_bindgen_test;
+use web_sys::{
+    js_sys::{Array, Object, Reflect, Uint8Array},
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Raw {
+    #[serde(with = "keplr::encoding::uint8array")]
+    bytes: Vec<u8>,
+}
+
+fn object(bytes: &JsValue) -> JsValue {
+    let object = Object::new();
+    Reflect::set(&object, &"bytes".into(), bytes).unwrap();
+    object.into()
+}
+
+#[wasm_bindgen_test]
+fn serializes_to_uint8array() {
+    let raw = Raw {
+        bytes: vec![0, 1, 255],
+    };
+    let value = serde_wasm_bindgen::to_value(&raw).unwrap();
+    let bytes = Reflect::get(&value, &"bytes".into()).unwrap();
+    assert!(bytes.is_instance_of::<Uint8Array>());
+    assert_eq!(bytes.unchecked_into::<Uint8Array>().to_vec(), [0, 1, 255]);
+}
+
+#[wasm_bindgen_test]
+fn deserializes_uint8array() {
+    let bytes = Uint8Array::from(&[0, 1, 255][..]);
+    let raw: Raw = serde_wasm_bindgen::from_value(object(&bytes)).unwrap();
+    assert_eq!(raw.bytes, [0, 1, 255]);
+}
+
+#[wasm_bindgen_test]
+fn deserializes_arrays_of_numbers() {
+    let bytes: Array = [0, 1, 255].into_iter().map(JsValue::from).collect();
+    let raw: Raw = serde_wasm_bindgen::from_value(object(&bytes)).unwrap();
+    assert_eq!(raw.bytes, [0, 1, 255]);
+}
+
+#[wasm_bindgen_test]
+fn round_trips() {
+    let raw = Raw {
+        bytes: vec![2, 170, 187, 204],
+    };
+    let value = serde_wasm_bindgen::to_value(&raw).unwrap();
+    assert_eq!(serde_wasm_bindgen::from_value::<Raw>(value).unwrap(), raw);
+}
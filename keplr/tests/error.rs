@@ -0,0 +1,31 @@
+use keplr::{EnableReport, Error};
+
+#[test]
+fn detects_unknown_chains() {
+    let error = Error::JavaScript("There is no chain info for localsecret".to_string());
+    assert_eq!(error.unknown_chain_id(), Some("localsecret"));
+
+    let error = Error::JavaScript("There is no modular chain info for pulsar-3".to_string());
+    assert_eq!(error.unknown_chain_id(), Some("pulsar-3"));
+
+    let error = Error::JavaScript("Request rejected".to_string());
+    assert_eq!(error.unknown_chain_id(), None);
+    let error = Error::Request("There is no chain info for secret-4".to_string());
+    assert_eq!(error.unknown_chain_id(), None);
+}
+
+#[test]
+fn enable_report_is_complete_without_failures() {
+    let mut report = EnableReport {
+        enabled: vec!["secret-4".to_string(), "localsecret".to_string()],
+        suggested: vec!["localsecret".to_string()],
+        ..Default::default()
+    };
+    assert!(report.is_complete());
+
+    report.failed.insert(
+        "pulsar-3".to_string(),
+        Error::JavaScript("Request rejected".to_string()),
+    );
+    assert!(!report.is_complete());
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "fee")]
+
+use keplr::{
+    fee::{fee_for_gas, FeeEstimator, GasInfo, GasPriceLevel},
+    lcd::Lcd,
+    sign_types::{Coin, StdFee},
+    suggest_chain_types::{FeeCurrency, GasPriceStep},
+    Error,
+};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn uscrt() -> FeeCurrency {
+    FeeCurrency {
+        coin_denom: "SCRT".to_string(),
+        coin_minimal_denom: "uscrt".to_string(),
+        coin_decimals: 6,
+        coin_gecko_id: "secret".to_string(),
+        gas_price_step: GasPriceStep {
+            low: 0.1,
+            average: 0.25,
+            high: 0.5,
+        },
+    }
+}
+
+async fn simulation(response: ResponseTemplate) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/cosmos/tx/v1beta1/simulate"))
+        .and(body_json(json!({ "tx_bytes": "AQID" })))
+        .respond_with(response)
+        .mount(&server)
+        .await;
+    server
+}
+
+#[test]
+fn computes_fee_for_gas() {
+    assert_eq!(
+        fee_for_gas(100_001, &uscrt(), GasPriceLevel::Low),
+        StdFee {
+            amount: vec![Coin {
+                denom: "uscrt".to_string(),
+                amount: "10001".to_string(),
+            }],
+            gas: "100001".to_string(),
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        fee_for_gas(100_000, &uscrt(), GasPriceLevel::High).amount[0].amount,
+        "50000"
+    );
+}
+
+#[tokio::test]
+async fn estimates_fee_from_simulation() {
+    let server = simulation(ResponseTemplate::new(200).set_body_json(json!({
+        "gas_info": { "gas_wanted": "0", "gas_used": "80000" },
+        "result": { "data": "", "log": "", "events": [] },
+    })))
+    .await;
+    let estimator = FeeEstimator::new(Lcd::new(format!("{}/", server.uri())));
+
+    assert_eq!(
+        estimator.simulate(&[1, 2, 3]).await.unwrap(),
+        GasInfo {
+            gas_wanted: 0,
+            gas_used: 80_000
+        }
+    );
+
+    let fee = estimator
+        .gas_adjustment(1.5)
+        .estimate(&[1, 2, 3], &uscrt(), GasPriceLevel::Average)
+        .await
+        .unwrap();
+    assert_eq!(fee.gas, "120000");
+    assert_eq!(fee.amount[0].amount, "30000");
+}
+
+#[tokio::test]
+async fn reports_simulation_errors() {
+    let server = simulation(ResponseTemplate::new(400).set_body_json(json!({
+        "code": 5,
+        "message": "insufficient funds",
+        "details": [],
+    })))
+    .await;
+    let error = FeeEstimator::new(Lcd::new(server.uri()))
+        .simulate(&[1, 2, 3])
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error,
+        Error::Request("400 Bad Request: insufficient funds".to_string())
+    );
+}
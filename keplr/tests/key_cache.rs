@@ -0,0 +1,129 @@
+use futures::{
+    channel::oneshot,
+    executor::{block_on, LocalPool},
+    future::{join, FutureExt},
+    task::LocalSpawnExt,
+};
+use keplr::{key_cache::KeyCache, Error, Key};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+fn key(name: &str) -> Key {
+    Key {
+        name: name.to_string(),
+        bech32_address: format!("secret1{name}"),
+        ..Default::default()
+    }
+}
+
+/// A cache whose fetcher returns the next key of `keys` and counts its calls.
+fn cache(keys: Vec<Result<Key, Error>>) -> (KeyCache, Rc<Cell<usize>>) {
+    let calls = Rc::new(Cell::new(0));
+    let keys = Rc::new(RefCell::new(keys.into_iter()));
+    let cache = KeyCache::with_fetcher({
+        let calls = calls.clone();
+        move |_chain_id| {
+            calls.set(calls.get() + 1);
+            let key = keys.borrow_mut().next().expect("no more keys");
+            async move { key }.boxed_local()
+        }
+    });
+    (cache, calls)
+}
+
+#[test]
+fn coalesces_concurrent_requests() {
+    let calls = Rc::new(Cell::new(0));
+    let (respond, response) = oneshot::channel::<Key>();
+    let response = response.shared();
+    let cache = KeyCache::with_fetcher({
+        let calls = calls.clone();
+        move |_chain_id| {
+            calls.set(calls.get() + 1);
+            let response = response.clone();
+            async move { response.await.map_err(|_| Error::Cancelled) }.boxed_local()
+        }
+    });
+
+    let mut pool = LocalPool::new();
+    let requests = {
+        let cache = cache.clone();
+        async move { join(cache.get("secret-4"), cache.get("secret-4")).await }
+    };
+    let both = pool.spawner().spawn_local_with_handle(requests).unwrap();
+    pool.run_until_stalled();
+    assert_eq!(calls.get(), 1);
+    assert_eq!(cache.peek("secret-4"), None);
+
+    respond.send(key("alice")).unwrap();
+    let (first, second) = pool.run_until(both);
+    assert_eq!(first, Ok(key("alice")));
+    assert_eq!(second, Ok(key("alice")));
+    assert_eq!(cache.peek("secret-4"), Some(key("alice")));
+
+    block_on(cache.get("secret-4")).unwrap();
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn does_not_cache_errors() {
+    let (cache, calls) = cache(vec![Err(Error::Cancelled), Ok(key("alice"))]);
+    assert_eq!(block_on(cache.get("secret-4")), Err(Error::Cancelled));
+    assert_eq!(cache.peek("secret-4"), None);
+
+    assert_eq!(block_on(cache.get("secret-4")), Ok(key("alice")));
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn caches_per_chain() {
+    let (cache, calls) = cache(vec![Ok(key("alice")), Ok(key("bob"))]);
+    assert_eq!(block_on(cache.get("secret-4")), Ok(key("alice")));
+    assert_eq!(block_on(cache.get("pulsar-3")), Ok(key("bob")));
+    assert_eq!(block_on(cache.get("secret-4")), Ok(key("alice")));
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn invalidates_and_clears() {
+    let (cache, calls) = cache(vec![
+        Ok(key("alice")),
+        Ok(key("bob")),
+        Ok(key("carol")),
+        Ok(key("dave")),
+    ]);
+    block_on(cache.get("secret-4")).unwrap();
+    block_on(cache.get("pulsar-3")).unwrap();
+
+    cache.invalidate("secret-4");
+    assert_eq!(cache.peek("secret-4"), None);
+    assert_eq!(cache.peek("pulsar-3"), Some(key("bob")));
+    assert_eq!(block_on(cache.get("secret-4")), Ok(key("carol")));
+
+    cache.clear();
+    assert_eq!(cache.peek("secret-4"), None);
+    assert_eq!(cache.peek("pulsar-3"), None);
+    assert_eq!(block_on(cache.get("pulsar-3")), Ok(key("dave")));
+    assert_eq!(calls.get(), 4);
+}
+
+#[test]
+fn drops_keys_when_the_generation_changes() {
+    let (cache, calls) = cache(vec![Ok(key("alice")), Ok(key("bob"))]);
+    let disabled = Rc::new(Cell::new(3));
+    let cache = cache.with_generation({
+        let disabled = disabled.clone();
+        move || disabled.get()
+    });
+
+    block_on(cache.get("secret-4")).unwrap();
+    assert_eq!(cache.peek("secret-4"), Some(key("alice")));
+
+    disabled.set(4);
+    assert_eq!(cache.peek("secret-4"), None);
+    assert_eq!(block_on(cache.get("secret-4")), Ok(key("bob")));
+    assert_eq!(calls.get(), 2);
+    assert!(cache.keplr().is_none());
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "presets")]
+
+use keplr::presets::{self, LOCALSECRET, PULSAR, SECRET};
+use std::collections::BTreeSet;
+
+#[test]
+fn secret_presets_share_the_network_config() {
+    for chain_info in [&*SECRET, &*PULSAR, &*LOCALSECRET] {
+        assert_eq!(chain_info.bip44.coin_type, 529);
+        assert_eq!(chain_info.bech32_config.bech32_prefix_acc_addr, "secret");
+        assert_eq!(chain_info.stake_currency.coin_minimal_denom, "uscrt");
+        assert_eq!(chain_info.fee_currencies[0].gas_price_step.average, 0.25);
+    }
+    assert_eq!(LOCALSECRET.chain_id, "secretdev-1");
+    assert_eq!(LOCALSECRET.rest, "http://localhost:1317");
+}
+
+#[test]
+fn finds_presets_by_chain_id() {
+    let chain_ids: BTreeSet<_> = presets::all()
+        .iter()
+        .map(|chain_info| chain_info.chain_id.as_str())
+        .collect();
+    assert_eq!(chain_ids.len(), presets::all().len());
+
+    assert_eq!(
+        presets::find("pulsar-3").unwrap().chain_name,
+        "Secret Network Testnet"
+    );
+    assert_eq!(
+        presets::find("osmosis-1")
+            .unwrap()
+            .stake_currency
+            .coin_denom,
+        "OSMO"
+    );
+    assert!(presets::find("juno-1").is_none());
+}
+
+#[test]
+fn serializes_without_empty_gecko_ids() {
+    let json = serde_json::to_value(&*LOCALSECRET).unwrap();
+    assert_eq!(json["chainId"], "secretdev-1");
+    assert_eq!(json["bech32Config"]["bech32PrefixValAddr"], "secretvaloper");
+    assert!(json["stakeCurrency"].get("coinGeckoId").is_none());
+    assert_eq!(json["feeCurrencies"][0]["gasPriceStep"]["high"], 0.5);
+}
@@ -0,0 +1,90 @@
+use futures::{channel::oneshot, executor::LocalPool, task::LocalSpawnExt};
+use keplr::{queue::RequestQueue, Error};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn runs_requests_one_at_a_time_in_order() {
+    let mut pool = LocalPool::new();
+    let queue = RequestQueue::new();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut popups = Vec::new();
+
+    for name in ["enable", "suggest_chain", "sign_amino"] {
+        let (close, closed) = oneshot::channel::<()>();
+        popups.push(close);
+        let (queue, log) = (queue.clone(), log.clone());
+        pool.spawner()
+            .spawn_local(async move {
+                queue
+                    .run(async {
+                        log.borrow_mut().push(format!("{name} opened"));
+                        closed.await.ok();
+                        log.borrow_mut().push(format!("{name} closed"));
+                    })
+                    .await
+            })
+            .unwrap();
+    }
+
+    pool.run_until_stalled();
+    assert_eq!(*log.borrow(), ["enable opened"]);
+    assert_eq!(queue.len(), 3);
+
+    // closing the popups out of order still runs the requests in order
+    let mut popups = popups.into_iter();
+    let (enable, suggest_chain, sign_amino) = (
+        popups.next().unwrap(),
+        popups.next().unwrap(),
+        popups.next().unwrap(),
+    );
+    sign_amino.send(()).unwrap();
+    enable.send(()).unwrap();
+    pool.run_until_stalled();
+    suggest_chain.send(()).unwrap();
+    pool.run_until_stalled();
+
+    assert_eq!(
+        *log.borrow(),
+        [
+            "enable opened",
+            "enable closed",
+            "suggest_chain opened",
+            "suggest_chain closed",
+            "sign_amino opened",
+            "sign_amino closed",
+        ]
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn failed_and_dropped_requests_hand_over() {
+    let mut pool = LocalPool::new();
+    let queue = RequestQueue::new();
+
+    let failed = pool.run_until(
+        queue.run(async { Err::<(), _>(Error::Signature("Request rejected".to_string())) }),
+    );
+    assert_eq!(
+        failed,
+        Err(Error::Signature("Request rejected".to_string()))
+    );
+
+    let (_close, closed) = oneshot::channel::<()>();
+    let abandoned = pool
+        .spawner()
+        .spawn_local_with_handle({
+            let queue = queue.clone();
+            async move {
+                queue.run(closed).await.ok();
+            }
+        })
+        .unwrap();
+    pool.run_until_stalled();
+    assert_eq!(queue.len(), 1);
+    drop(abandoned);
+    pool.run_until_stalled();
+
+    assert_eq!(pool.run_until(queue.run(async { 42 })), 42);
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,31 @@
+use keplr::{
+    send::{send, SendKeplr},
+    suggest_chain_types::SuggestingChainInfo,
+    EnableReport,
+};
+use std::{collections::BTreeMap, future::Future, rc::Rc};
+
+fn assert_send<T: Send>(value: T) -> T {
+    value
+}
+
+// Only type checked, constructing a `Keplr` needs a browser.
+#[allow(dead_code)]
+fn keplr_futures_are_send(keplr: &SendKeplr) -> impl Future + Send + '_ {
+    assert_send(keplr.get_key("secret-4"))
+}
+
+#[allow(dead_code)]
+fn enable_or_suggest_is_send<'a>(
+    keplr: &'a SendKeplr,
+    chains: &'a BTreeMap<String, SuggestingChainInfo>,
+) -> impl Future<Output = EnableReport> + Send + 'a {
+    assert_send(keplr.enable_or_suggest(chains))
+}
+
+#[test]
+fn wraps_non_send_futures() {
+    let value = Rc::new(42);
+    let future = assert_send(send(async move { *value }));
+    assert_eq!(futures::executor::block_on(future), 42);
+}
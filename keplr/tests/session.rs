@@ -0,0 +1,204 @@
+use futures::executor::block_on;
+use keplr::{
+    session::{
+        fingerprint, restore_with, MemoryStorage, RestoredSession, Session, SessionStorage,
+        SessionStore, DEFAULT_STORAGE_KEY,
+    },
+    Error, Key,
+};
+use std::{cell::Cell, rc::Rc};
+
+fn key(pub_key: Vec<u8>) -> Key {
+    Key {
+        name: "alice".to_string(),
+        pub_key,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn fingerprints_the_public_key() {
+    let mut pub_key = vec![7; 33];
+    pub_key[0] = 2;
+    assert_eq!(fingerprint(&key(pub_key.clone())), "81560c27f0dc97a3");
+
+    pub_key[32] = 8;
+    assert_ne!(fingerprint(&key(pub_key)), "81560c27f0dc97a3");
+}
+
+#[test]
+fn session_is_stored_as_json() {
+    let session = Session {
+        chain_ids: vec!["secret-4".to_string(), "pulsar-3".to_string()],
+        key_fingerprint: Some("81560c27f0dc97a3".to_string()),
+    };
+    let json = serde_json::to_string(&session).unwrap();
+    assert_eq!(
+        json,
+        r#"{"chainIds":["secret-4","pulsar-3"],"keyFingerprint":"81560c27f0dc97a3"}"#
+    );
+    assert_eq!(serde_json::from_str::<Session>(&json).unwrap(), session);
+}
+
+#[test]
+fn stores_under_a_key() {
+    assert_eq!(SessionStore::default().key(), DEFAULT_STORAGE_KEY);
+    assert_eq!(SessionStore::new("my-app").key(), "my-app");
+}
+
+/// Counts writes to the wrapped storage.
+#[derive(Clone, Default)]
+struct CountingStorage {
+    inner: MemoryStorage,
+    writes: Rc<Cell<usize>>,
+}
+
+impl SessionStorage for CountingStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, Error> {
+        self.inner.get(key)
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<(), Error> {
+        self.writes.set(self.writes.get() + 1);
+        self.inner.set(key, value)
+    }
+
+    fn remove(&self, key: &str) -> Result<(), Error> {
+        self.writes.set(self.writes.get() + 1);
+        self.inner.remove(key)
+    }
+}
+
+fn session(chain_ids: &[&str], key_fingerprint: Option<String>) -> Session {
+    Session {
+        chain_ids: chain_ids.iter().map(|id| id.to_string()).collect(),
+        key_fingerprint,
+    }
+}
+
+#[test]
+fn update_starts_changes_and_clears_sessions() {
+    let storage = CountingStorage::default();
+    let store = SessionStore::with_storage("test", storage.clone());
+
+    store
+        .update(|session| session.chain_ids.push("secret-4".to_string()))
+        .unwrap();
+    assert_eq!(store.load().unwrap(), Some(session(&["secret-4"], None)));
+
+    // unchanged sessions aren't written again
+    store.update(|session| session.chain_ids.dedup()).unwrap();
+    assert_eq!(storage.writes.get(), 1);
+
+    store.update(|session| session.chain_ids.clear()).unwrap();
+    assert_eq!(store.load().unwrap(), None);
+    assert_eq!(storage.writes.get(), 2);
+
+    // nothing to clear
+    store.update(|_| {}).unwrap();
+    assert_eq!(storage.writes.get(), 2);
+}
+
+fn restore(
+    store: &SessionStore,
+    keys: impl Fn(&str) -> Result<Key, Error>,
+) -> Result<Option<RestoredSession>, Error> {
+    block_on(restore_with(store, |chain_id| {
+        let key = keys(&chain_id);
+        async move { key }
+    }))
+}
+
+#[test]
+fn restores_sessions() {
+    let store = SessionStore::with_storage("test", MemoryStorage::new());
+    assert_eq!(restore(&store, |_| unreachable!()), Ok(None));
+
+    let alice = key(vec![2; 33]);
+    store
+        .save(&session(
+            &["secret-4", "pulsar-3"],
+            Some(fingerprint(&alice)),
+        ))
+        .unwrap();
+    let restored = restore(&store, |_| Ok(alice.clone())).unwrap().unwrap();
+    assert_eq!(
+        restored,
+        RestoredSession {
+            session: session(&["secret-4", "pulsar-3"], Some(fingerprint(&alice))),
+            account_changed: false,
+            skipped: vec![],
+        }
+    );
+}
+
+#[test]
+fn reports_account_changes() {
+    let store = SessionStore::with_storage("test", MemoryStorage::new());
+    let (alice, bob) = (key(vec![2; 33]), key(vec![3; 33]));
+    store
+        .save(&session(
+            &["secret-4", "pulsar-3"],
+            Some(fingerprint(&alice)),
+        ))
+        .unwrap();
+
+    // the fingerprint is the one of the first chain's key
+    let restored = restore(&store, |chain_id| match chain_id {
+        "secret-4" => Ok(bob.clone()),
+        _ => Ok(alice.clone()),
+    })
+    .unwrap()
+    .unwrap();
+    assert!(restored.account_changed);
+    assert_eq!(restored.session.key_fingerprint, Some(fingerprint(&bob)));
+    assert_eq!(store.load().unwrap(), Some(restored.session));
+}
+
+#[test]
+fn clears_sessions_the_wallet_rejects() {
+    let store = SessionStore::with_storage("test", MemoryStorage::new());
+    store
+        .save(&session(&["secret-4", "pulsar-3"], None))
+        .unwrap();
+
+    let restored = restore(&store, |chain_id| match chain_id {
+        "secret-4" => Ok(key(vec![2; 33])),
+        _ => Err(Error::JavaScript("Request rejected".to_string())),
+    });
+    assert_eq!(restored, Ok(None));
+    assert_eq!(store.load().unwrap(), None);
+}
+
+#[test]
+fn keeps_sessions_on_transient_errors() {
+    let store = SessionStore::with_storage("test", MemoryStorage::new());
+    let saved = session(&["secret-4"], Some("81560c27f0dc97a3".to_string()));
+    store.save(&saved).unwrap();
+
+    for error in [Error::Cancelled, Error::KeplrUnavailable] {
+        assert_eq!(restore(&store, |_| Err(error.clone())), Err(error));
+        assert_eq!(store.load().unwrap(), Some(saved.clone()));
+    }
+}
+
+#[test]
+fn skips_chains_the_wallet_doesnt_answer_for() {
+    let store = SessionStore::with_storage("test", MemoryStorage::new());
+    let alice = key(vec![2; 33]);
+    let saved = session(&["secret-4", "pulsar-3"], Some(fingerprint(&alice)));
+    store.save(&saved).unwrap();
+
+    // the first chain's key can't tell whether the account changed
+    let restored = restore(&store, |chain_id| match chain_id {
+        "secret-4" => Err(Error::Timeout("locked".to_string())),
+        _ => Ok(key(vec![3; 33])),
+    })
+    .unwrap()
+    .unwrap();
+    assert!(!restored.account_changed);
+    assert_eq!(restored.skipped, ["secret-4"]);
+    assert_eq!(restored.chain_ids().collect::<Vec<_>>(), ["pulsar-3"]);
+    assert_eq!(restored.session, saved);
+    assert_eq!(store.load().unwrap(), Some(saved));
+}
@@ -0,0 +1,201 @@
+use keplr::sign_types::*;
+use serde_json::json;
+
+#[test]
+fn sign_options_skip_unset_fields() {
+    let options = SignOptions {
+        prefer_no_set_fee: Some(true),
+        disable_balance_check: Some(false),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&options).unwrap(),
+        json!({ "preferNoSetFee": true, "disableBalanceCheck": false })
+    );
+}
+
+#[test]
+fn sign_options_merge_overrides() {
+    let defaults = SignOptions {
+        prefer_no_set_fee: Some(true),
+        prefer_no_set_memo: Some(true),
+        ..Default::default()
+    };
+    let overrides = SignOptions {
+        prefer_no_set_memo: Some(false),
+        disable_balance_check: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(
+        defaults.merge(&overrides),
+        SignOptions {
+            prefer_no_set_fee: Some(true),
+            prefer_no_set_memo: Some(false),
+            disable_balance_check: Some(true),
+        }
+    );
+}
+
+#[test]
+fn sign_doc_accepts_long_account_number() {
+    let sign_doc: SignDoc = serde_json::from_value(json!({
+        "bodyBytes": [1, 2],
+        "authInfoBytes": [3],
+        "chainId": "secret-4",
+        "accountNumber": { "low": -1, "high": 1, "unsigned": false },
+    }))
+    .unwrap();
+    assert_eq!(
+        sign_doc.account_number,
+        (u64::from(u32::MAX) + (1 << 32)).to_string()
+    );
+    assert_eq!(sign_doc.body_bytes, vec![1, 2]);
+
+    let sign_doc: SignDoc = serde_json::from_value(json!({
+        "bodyBytes": [],
+        "authInfoBytes": [],
+        "chainId": "secret-4",
+        "accountNumber": "42",
+    }))
+    .unwrap();
+    assert_eq!(sign_doc.account_number, "42");
+}
+
+#[test]
+fn std_sign_doc_uses_amino_field_names() {
+    let sign_doc = StdSignDoc {
+        chain_id: "secret-4".to_string(),
+        account_number: "1".to_string(),
+        sequence: "2".to_string(),
+        fee: StdFee {
+            amount: vec![Coin {
+                denom: "uscrt".to_string(),
+                amount: "5000".to_string(),
+            }],
+            gas: "200000".to_string(),
+            ..Default::default()
+        },
+        msgs: vec![AminoMsg {
+            type_: "cosmos-sdk/MsgSend".to_string(),
+            value: json!({ "amount": [] }),
+        }],
+        memo: String::new(),
+    };
+    let value = serde_json::to_value(&sign_doc).unwrap();
+    assert_eq!(value["chain_id"], json!("secret-4"));
+    assert_eq!(value["msgs"][0]["type"], json!("cosmos-sdk/MsgSend"));
+    assert!(value["fee"].get("granter").is_none());
+    assert_eq!(
+        serde_json::from_value::<StdSignDoc>(value).unwrap(),
+        sign_doc
+    );
+}
+
+const PUBKEY: &str = "A8AqmnlDQbdOn1jNcDv3swfH6XwBsXTiAurst30M/jNr";
+
+fn signature(signature: &str) -> StdSignature {
+    StdSignature {
+        pub_key: PubKey {
+            type_: "tendermint/PubKeySecp256k1".to_string(),
+            value: PUBKEY.to_string(),
+        },
+        signature: signature.to_string(),
+    }
+}
+
+fn send_doc() -> StdSignDoc {
+    StdSignDoc {
+        chain_id: "secret-4".to_string(),
+        account_number: "12".to_string(),
+        sequence: "3".to_string(),
+        fee: StdFee {
+            amount: vec![Coin {
+                denom: "uscrt".to_string(),
+                amount: "2500".to_string(),
+            }],
+            gas: "100000".to_string(),
+            ..Default::default()
+        },
+        msgs: vec![AminoMsg {
+            type_: "cosmos-sdk/MsgSend".to_string(),
+            value: json!({
+                "from_address": "secret1from",
+                "to_address": "secret1to",
+                "amount": [{ "denom": "uscrt", "amount": "1" }],
+            }),
+        }],
+        memo: "<script>&</script>".to_string(),
+    }
+}
+
+#[test]
+fn sign_bytes_match_keplr() {
+    assert_eq!(
+        String::from_utf8(send_doc().sign_bytes().unwrap()).unwrap(),
+        r#"{"account_number":"12","chain_id":"secret-4","fee":{"amount":[{"amount":"2500","denom":"uscrt"}],"gas":"100000"},"memo":"\u003cscript\u003e\u0026\u003c/script\u003e","msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1","denom":"uscrt"}],"from_address":"secret1from","to_address":"secret1to"}}],"sequence":"3"}"#
+    );
+    assert_eq!(
+        String::from_utf8(
+            StdSignDoc::arbitrary("secret1signer", b"hello")
+                .sign_bytes()
+                .unwrap()
+        )
+        .unwrap(),
+        r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"secret1signer"}}],"sequence":"0"}"#
+    );
+}
+
+#[test]
+fn verifies_amino_signatures() {
+    let signature = signature(
+        "ZKwZVdUmAhB6jJEE1UFtc1EInIbcJ/HjaKoTWpow4WhgGL18GJUb1hR0+AqV14KzazPcfnI6Q7Dq7eB66B24qg==",
+    );
+    assert!(signature.verify(&send_doc()).unwrap());
+
+    let mut tampered = send_doc();
+    tampered.memo = "<script>&</script> ".to_string();
+    assert!(!signature.verify(&tampered).unwrap());
+}
+
+#[test]
+fn verifies_arbitrary_signatures() {
+    let signature = signature(
+        "fMgef/QZN3VzeSA6K4Gp2cHGialT3s07l0wPsI0eP4geM4oOyQBtus5kI68iBccKKk6a6MQv06HUtF73y29JOg==",
+    );
+    assert!(signature
+        .verify(&StdSignDoc::arbitrary("secret1signer", b"hello"))
+        .unwrap());
+    assert!(!signature
+        .verify(&StdSignDoc::arbitrary("secret1signer", b"hello!"))
+        .unwrap());
+}
+
+#[test]
+fn rejects_unsupported_keys() {
+    let mut signature = signature("");
+    signature.pub_key.type_ = "tendermint/PubKeyEd25519".to_string();
+    assert!(matches!(
+        signature.verify(&send_doc()),
+        Err(keplr::Error::Signature(_))
+    ));
+}
+
+/// The response to `keplr.signAmino` for a MsgSend on LocalSecret, signed by its test account
+/// `a` (the `grant rice ...` mnemonic, coin type 529). Signatures are deterministic (RFC 6979),
+/// so the wallet returns exactly these bytes for this key and doc.
+const SIGN_AMINO_RESPONSE: &str = include_str!("fixtures/keplr/sign-amino-localsecret.json");
+
+#[test]
+fn verifies_a_sign_amino_response_with_html_characters_in_the_memo() {
+    let response: AminoSignResponse = serde_json::from_str(SIGN_AMINO_RESPONSE).unwrap();
+    assert_eq!(response.signed.memo, "fish & chips <for> you");
+    assert_eq!(
+        String::from_utf8(response.signed.sign_bytes().unwrap()).unwrap(),
+        r#"{"account_number":"0","chain_id":"secretdev-1","fee":{"amount":[{"amount":"6250","denom":"uscrt"}],"gas":"25000"},"memo":"fish \u0026 chips \u003cfor\u003e you","msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1000000","denom":"uscrt"}],"from_address":"secret1ap26qrlp8mcq2pg6r47w43l0y8zkqm8a450s03","to_address":"secret1fc3fzy78ttp0lwuujw7e52rhspxn8uj52zfyne"}}],"sequence":"4"}"#
+    );
+    assert!(response.signature.verify(&response.signed).unwrap());
+
+    let mut tampered = response.signed.clone();
+    tampered.memo = "fish & chips <for> me".to_string();
+    assert!(!response.signature.verify(&tampered).unwrap());
+}
@@ -0,0 +1,35 @@
+use keplr::{
+    timeout::{abortable, timeout},
+    Error,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn fails_when_the_wallet_never_answers() {
+    let never = futures::future::pending::<Result<(), Error>>();
+    let result = timeout(Duration::from_millis(10), never).await;
+    assert!(matches!(result, Err(Error::Timeout(_))));
+}
+
+#[tokio::test]
+async fn passes_through_results_in_time() {
+    let result = timeout(Duration::from_secs(5), async { Ok::<_, Error>(42) }).await;
+    assert_eq!(result, Ok(42));
+
+    let error = Error::Signature("Request rejected".to_string());
+    let result = timeout(Duration::from_secs(5), async {
+        Err::<(), _>(error.clone())
+    })
+    .await;
+    assert_eq!(result, Err(error));
+}
+
+#[tokio::test]
+async fn aborting_cancels_the_request() {
+    let (request, handle) = abortable(futures::future::pending::<Result<(), Error>>());
+    handle.abort();
+    assert_eq!(request.await, Err(Error::Cancelled));
+
+    let (request, _handle) = abortable(async { Ok::<_, Error>("signed") });
+    assert_eq!(request.await, Ok("signed"));
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "cosmrs")]
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use cosmrs::{
+    proto::cosmos::bank::v1beta1::MsgSend,
+    tx::{ModeInfo, SignMode, Tx},
+    Any,
+};
+use keplr::{
+    sign_types::{AminoSignResponse, Coin, PubKey, StdFee, StdSignature},
+    tx::TxBuilder,
+    AccountData, Algo, Error,
+};
+use serde_json::json;
+
+const ADDRESS: &str = "cosmos1h806c7khnvmjlywdrkdgk2vrayy2mmvf9rxk2r";
+
+fn account() -> AccountData {
+    AccountData {
+        address: ADDRESS.to_string(),
+        algo: Algo::Secp256k1,
+        pubkey: BASE64_STANDARD
+            .decode("AtQaCqFnshaZQp6rIkvAPyzThvCvXSDO+9AzbxVErqJP")
+            .unwrap(),
+    }
+}
+
+fn msg_send() -> Any {
+    Any::from_msg(&MsgSend {
+        from_address: ADDRESS.to_string(),
+        to_address: "cosmos1to".to_string(),
+        amount: vec![cosmrs::proto::cosmos::base::v1beta1::Coin {
+            denom: "uatom".to_string(),
+            amount: "1000".to_string(),
+        }],
+    })
+    .unwrap()
+}
+
+fn fee(amount: &str, gas: &str) -> StdFee {
+    StdFee {
+        amount: vec![Coin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }],
+        gas: gas.to_string(),
+        ..Default::default()
+    }
+}
+
+fn builder() -> TxBuilder {
+    TxBuilder::new()
+        .message(msg_send())
+        .memo("hello")
+        .account_number(42)
+        .sequence(7)
+}
+
+#[test]
+fn encodes_simulation_tx() {
+    let tx = Tx::from_bytes(&builder().simulation_bytes(&account()).unwrap()).unwrap();
+    assert_eq!(tx.body.messages, [msg_send()]);
+    assert_eq!(tx.body.memo, "hello");
+    assert_eq!(tx.auth_info.fee.gas_limit, 0);
+    assert!(tx.auth_info.fee.amount.is_empty());
+    let signer_info = &tx.auth_info.signer_infos[0];
+    assert_eq!(signer_info.sequence, 7);
+    assert_eq!(
+        signer_info.public_key,
+        Some(account().public_key().unwrap().into())
+    );
+    // one empty signature per signer
+    assert_eq!(tx.signatures, [Vec::<u8>::new()]);
+
+    let bytes = builder()
+        .fee(fee("500", "200000"))
+        .simulation_bytes(&account())
+        .unwrap();
+    assert_eq!(
+        Tx::from_bytes(&bytes).unwrap().auth_info.fee.gas_limit,
+        200_000
+    );
+}
+
+#[test]
+fn builds_amino_sign_doc() {
+    let sign_doc = builder()
+        .fee(fee("500", "200000"))
+        .amino_sign_doc("cosmoshub-4")
+        .unwrap();
+    assert_eq!(sign_doc.chain_id, "cosmoshub-4");
+    assert_eq!(sign_doc.account_number, "42");
+    assert_eq!(sign_doc.sequence, "7");
+    assert_eq!(sign_doc.fee, fee("500", "200000"));
+    assert_eq!(sign_doc.memo, "hello");
+    assert_eq!(sign_doc.msgs[0].type_, "cosmos-sdk/MsgSend");
+    assert_eq!(
+        sign_doc.msgs[0].value["amount"],
+        json!([{ "denom": "uatom", "amount": "1000" }])
+    );
+}
+
+fn response(builder: &TxBuilder, signature: &str) -> AminoSignResponse {
+    let mut signed = builder.amino_sign_doc("cosmoshub-4").unwrap();
+    // the user raised the fee and changed the memo in the wallet
+    signed.fee = fee("800", "250000");
+    signed.memo = "edited".to_string();
+    AminoSignResponse {
+        signed,
+        signature: StdSignature {
+            pub_key: PubKey {
+                type_: "tendermint/PubKeySecp256k1".to_string(),
+                value: BASE64_STANDARD.encode(account().pubkey),
+            },
+            signature: signature.to_string(),
+        },
+    }
+}
+
+#[test]
+fn rebuilds_tx_from_amino_response() {
+    let builder = builder().fee(fee("500", "200000"));
+    let response = response(&builder, &BASE64_STANDARD.encode([9; 64]));
+    let tx = Tx::from_bytes(&builder.amino_tx(&account(), response).unwrap()).unwrap();
+
+    assert_eq!(tx.body.messages, [msg_send()]);
+    assert_eq!(tx.body.memo, "edited");
+    assert_eq!(tx.auth_info.fee.gas_limit, 250_000);
+    assert_eq!(tx.auth_info.fee.amount[0].amount, 800);
+    let signer_info = &tx.auth_info.signer_infos[0];
+    assert_eq!(signer_info.sequence, 7);
+    assert!(matches!(
+        &signer_info.mode_info,
+        ModeInfo::Single(single) if single.mode == SignMode::LegacyAminoJson
+    ));
+    assert_eq!(tx.signatures, [vec![9; 64]]);
+}
+
+#[test]
+fn rejects_invalid_amino_responses() {
+    let builder = builder().fee(fee("500", "200000"));
+    assert!(matches!(
+        builder.amino_tx(&account(), response(&builder, "not base64!")),
+        Err(Error::Tx(_))
+    ));
+
+    let mut invalid = response(&builder, "");
+    invalid.signed.sequence = "seven".to_string();
+    assert_eq!(
+        builder.amino_tx(&account(), invalid),
+        Err(Error::Tx("invalid sequence".to_string()))
+    );
+}
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use keplr::{
+    events::EventListener,
+    provider::WalletProvider,
+    sign_types::StdSignature,
+    suggest_chain_types::SuggestingChainInfo,
+    wallet::{Wallet, WalletRegistry},
+    Error, Keplr, Key, OfflineSigner,
+};
+
+/// A wallet that only has a name, every request fails.
+struct FakeWallet {
+    name: &'static str,
+    id: u32,
+}
+
+impl FakeWallet {
+    fn new(name: &'static str, id: u32) -> Self {
+        Self { name, id }
+    }
+}
+
+fn unavailable<T>(wallet: &FakeWallet) -> Result<T, Error> {
+    Err(Error::WalletUnavailable(format!(
+        "{} #{}",
+        wallet.name, wallet.id
+    )))
+}
+
+#[async_trait(?Send)]
+impl Wallet for FakeWallet {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn enable(&self, _chain_ids: Vec<String>) -> Result<(), Error> {
+        unavailable(self)
+    }
+
+    async fn disable(&self, _chain_id: &str) -> Result<(), Error> {
+        unavailable(self)
+    }
+
+    async fn get_key(&self, _chain_id: &str) -> Result<Key, Error> {
+        unavailable(self)
+    }
+
+    async fn get_offline_signer(&self, _chain_id: &str) -> Result<OfflineSigner, Error> {
+        unavailable(self)
+    }
+
+    async fn suggest_chain(&self, _chain_info: &SuggestingChainInfo) -> Result<(), Error> {
+        unavailable(self)
+    }
+
+    async fn sign_arbitrary(
+        &self,
+        _chain_id: &str,
+        _signer: &str,
+        _data: &[u8],
+    ) -> Result<StdSignature, Error> {
+        unavailable(self)
+    }
+
+    fn on_keystore_change(&self, _callback: Box<dyn FnMut()>) -> Result<EventListener, Error> {
+        unavailable(self)
+    }
+}
+
+fn names(registry: &WalletRegistry) -> Vec<&str> {
+    registry
+        .wallets()
+        .iter()
+        .map(|wallet| wallet.name())
+        .collect()
+}
+
+#[test]
+fn registers_wallets_in_order() {
+    let mut registry = WalletRegistry::new();
+    assert!(registry.is_empty());
+
+    registry.register(FakeWallet::new("Leap", 0));
+    registry.register(FakeWallet::new("Ledger", 0));
+    registry.register(FakeWallet::new("Keplr", 0));
+
+    assert!(!registry.is_empty());
+    assert_eq!(names(&registry), ["Leap", "Ledger", "Keplr"]);
+    assert_eq!(format!("{registry:?}"), r#"["Leap", "Ledger", "Keplr"]"#);
+
+    let wallets = registry.into_wallets();
+    assert_eq!(wallets.len(), 3);
+    assert_eq!(wallets[2].name(), "Keplr");
+}
+
+#[tokio::test]
+async fn get_returns_the_first_wallet_with_the_name() {
+    let mut registry = WalletRegistry::new();
+    registry.register(FakeWallet::new("Keplr", 1));
+    registry.register(FakeWallet::new("Leap", 1));
+    registry.register(FakeWallet::new("Keplr", 2));
+
+    let keplr = registry.get("Keplr").unwrap();
+    assert_eq!(
+        keplr.enable(vec!["secret-4".to_string()]).await,
+        Err(Error::WalletUnavailable("Keplr #1".to_string()))
+    );
+    assert_eq!(registry.get("Leap").unwrap().name(), "Leap");
+    assert!(registry.get("keplr").is_none());
+    assert!(registry.get("Cosmostation").is_none());
+}
+
+// `WalletRegistry::detect` registers `Keplr` handles
+#[allow(dead_code)]
+fn keplr_is_a_wallet(mut registry: WalletRegistry, keplr: Keplr) {
+    registry.register(keplr);
+}
+
+#[test]
+fn detects_installed_providers_in_order() {
+    let mut looked_up = Vec::new();
+    let registry = WalletRegistry::detect_with(|provider| {
+        looked_up.push(provider);
+        (provider != WalletProvider::Keplr).then(|| FakeWallet::new(provider.name(), 0))
+    });
+
+    assert_eq!(looked_up, WalletProvider::ALL);
+    assert_eq!(names(&registry), ["Leap", "Cosmostation"]);
+    assert!(registry.get("Keplr").is_none());
+
+    assert!(WalletRegistry::detect_with(|_| None::<FakeWallet>).is_empty());
+}